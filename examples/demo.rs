@@ -1,12 +1,7 @@
 use chrono::{Months, NaiveDate};
 use std::time::Instant;
 
-mod dates;
-use dates::time_fractions::{ActualActualISDA, FixedBaseTimeFractionCalc, TimeFractionCalc};
-use dates::day_counting::{ActualCounter, Days30Counter, Days30ECounter};
-use dates::date_adjusting::{ModifiedFollowing, Following, Preceding, ModifiedPreceding, DateAdjustingMethod};
-use dates::calendars::{Calendar, get_ny_calendar};
-use dates::tenors::Tenor;
+use dates::prelude::*;
 
 
 fn main() {
@@ -48,38 +43,38 @@ fn main() {
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,2,29).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2023,2,28).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,3, 31).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,4, 30).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, None));
+    println!("{}", a.add_to_date(tenor_date, None).unwrap());
 
     println!("---");
     println!("---");
@@ -91,45 +86,45 @@ fn main() {
     let mf = ModifiedFollowing::new(&ny_calendar);
     let dadjm: Option<&dyn DateAdjustingMethod> = Some(&mf);
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2023,2,28).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,3, 31).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     println!("---");
     println!("---");
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,4, 30).unwrap();
     let a: Tenor = Tenor::from_str_opt("12m").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
     let a: Tenor = Tenor::from_str_opt("3w").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
 
     let tenor_date = NaiveDate::from_ymd_opt(2024,5, 31).unwrap();
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
 
     let tenor_date = NaiveDate::from_ymd_opt(2023,3, 23).unwrap();
     let a: Tenor = Tenor::from_str_opt("1y").unwrap();
-    println!("{}", a.add_to_date(tenor_date, dadjm));
+    println!("{}", a.add_to_date(tenor_date, dadjm).unwrap());
 }
\ No newline at end of file
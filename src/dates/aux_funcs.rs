@@ -1,7 +1,5 @@
 use chrono::{Datelike, NaiveDate};
 
-use crate::dates::calendars::Calendar;
-
 pub fn is_leap_year(yyyy: i32) -> bool {
     yyyy % 4 == 0 && yyyy % 100 != 0 || yyyy % 400 == 0
 }
@@ -19,4 +17,16 @@ pub fn get_eom(t: NaiveDate) -> NaiveDate {
 pub fn get_current_year_end_of_february(t: NaiveDate) -> NaiveDate {
     let yyyy: i32 = t.year();
     NaiveDate::from_ymd_opt(yyyy, 2, 28 + is_leap_year(yyyy) as u32).unwrap()
-}
\ No newline at end of file
+}
+
+// Number of 29th of February in (start_date, end_date].
+pub fn count_leap_days(start_date: NaiveDate, end_date: NaiveDate) -> i32 {
+    // Leap days in years [1, yyyy] up to date, excluded if date is before the 29th of February.
+    let leap_days_until = |t: NaiveDate| -> i32 {
+        let yyyy: i32 = t.year();
+        let full_years: i32 = yyyy - 1;
+        let leap_years: i32 = full_years / 4 - full_years / 100 + full_years / 400;
+        leap_years + (is_leap_year(yyyy) && t.ordinal() >= 60) as i32
+    };
+    leap_days_until(end_date) - leap_days_until(start_date)
+}
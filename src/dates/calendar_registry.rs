@@ -0,0 +1,136 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::dates::calendars::{
+    get_hong_kong_calendar, get_london_calendar, get_mexico_city_calendar, get_ny_calendar, get_riyadh_calendar, get_santiago_calendar,
+    get_sao_paulo_calendar, get_shanghai_calendar, get_sifma_calendar, get_singapore_calendar, get_sydney_calendar, get_target_calendar,
+    get_tokyo_calendar, get_toronto_calendar, get_zurich_calendar, Calendar,
+};
+
+// Process wide calendars keyed by business centre code (FpML style: USNY, GBLO, EUTA...). Codes joined with '+' resolve to
+// the union of the calendars (closed if any of them is closed), which is built once and cached.
+struct CalendarRegistry {
+    calendars: HashMap<String, Arc<Calendar>>,
+    combined: HashMap<String, Arc<Calendar>>,
+}
+impl CalendarRegistry {
+    fn with_built_in_calendars() -> Self {
+        let built_in: [(&str, Calendar); 16] = [
+            ("USNY", get_ny_calendar(None, None, None)),
+            ("USGS", get_sifma_calendar(None, None, None)),
+            ("GBLO", get_london_calendar(None, None, None)),
+            ("EUTA", get_target_calendar(None, None, None)),
+            ("JPTO", get_tokyo_calendar(None, None, None)),
+            ("CHZU", get_zurich_calendar(None, None, None)),
+            ("CATO", get_toronto_calendar(None, None, None)),
+            ("AUSY", get_sydney_calendar(None, None, None)),
+            ("BRSP", get_sao_paulo_calendar(None, None, None)),
+            ("CLSA", get_santiago_calendar(None, None, None)),
+            ("MXMC", get_mexico_city_calendar(None, None, None)),
+            ("HKHK", get_hong_kong_calendar(None, None, None)),
+            ("SGSI", get_singapore_calendar(None, None, None)),
+            ("CNBE", get_shanghai_calendar(None, None, None)),
+            ("CNSH", get_shanghai_calendar(None, None, None)),
+            ("SARI", get_riyadh_calendar(None, None, None)),
+        ];
+        CalendarRegistry {
+            calendars: built_in.into_iter().map(|(code, calendar)| (code.to_string(), Arc::new(calendar))).collect(),
+            combined: HashMap::new(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref REGISTRY: RwLock<CalendarRegistry> = RwLock::new(CalendarRegistry::with_built_in_calendars());
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCalendarError {
+    pub code: String,
+}
+impl fmt::Display for UnknownCalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown calendar code '{}'.", self.code)
+    }
+}
+impl Error for UnknownCalendarError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCalendarCodeError {
+    pub code: String,
+}
+impl fmt::Display for InvalidCalendarCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid calendar code '{}', codes can not be empty or contain '+'.", self.code)
+    }
+}
+impl Error for InvalidCalendarCodeError {}
+
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+// Sorted and without repetitions, so that "GBLO+USNY", "usny+gblo" and "USNY+GBLO+USNY" share the cached calendar.
+fn split_codes(code: &str) -> Result<Vec<String>, UnknownCalendarError> {
+    let mut codes: Vec<String> = code.split('+').map(normalize_code).collect();
+    if codes.iter().any(|c| c.is_empty()) {
+        return Err(UnknownCalendarError { code: code.to_string() });
+    }
+    codes.sort();
+    codes.dedup();
+    Ok(codes)
+}
+
+// Calendar for a code or a '+' joined combination of codes.
+pub fn get_calendar(code: &str) -> Result<Arc<Calendar>, UnknownCalendarError> {
+    let codes: Vec<String> = split_codes(code)?;
+    let key: String = codes.join("+");
+    {
+        let registry = REGISTRY.read().unwrap();
+        if let Some(calendar) = registry.calendars.get(&key).or_else(|| registry.combined.get(&key)) {
+            return Ok(Arc::clone(calendar));
+        }
+    }
+
+    let mut registry = REGISTRY.write().unwrap();
+    // Another thread may have built it while the lock was released.
+    if let Some(calendar) = registry.combined.get(&key) {
+        return Ok(Arc::clone(calendar));
+    }
+    let mut calendars: Vec<Arc<Calendar>> = Vec::with_capacity(codes.len());
+    for c in &codes {
+        let calendar: &Arc<Calendar> = registry.calendars.get(c).ok_or_else(|| UnknownCalendarError { code: c.clone() })?;
+        calendars.push(Arc::clone(calendar));
+    }
+    let combined: Calendar = calendars[1..].iter().fold(calendars[0].as_ref().clone(), |combined, calendar| combined.combine(calendar.as_ref().clone()));
+    let combined: Arc<Calendar> = Arc::new(combined);
+    registry.combined.insert(key, Arc::clone(&combined));
+    Ok(combined)
+}
+
+// Adds a calendar, or replaces the one registered with the same code. Cached combinations including it are dropped,
+// calendars already handed out are not affected.
+pub fn register_calendar(code: &str, calendar: Calendar) -> Result<(), InvalidCalendarCodeError> {
+    let normalized: String = normalize_code(code);
+    if normalized.is_empty() || normalized.contains('+') {
+        return Err(InvalidCalendarCodeError { code: code.to_string() });
+    }
+    let mut registry = REGISTRY.write().unwrap();
+    registry.combined.retain(|key, _| !key.split('+').any(|c| c == normalized));
+    registry.calendars.insert(normalized, Arc::new(calendar));
+    Ok(())
+}
+
+pub fn is_registered_calendar(code: &str) -> bool {
+    REGISTRY.read().unwrap().calendars.contains_key(&normalize_code(code))
+}
+
+// Registered codes, sorted (combinations are not included).
+pub fn get_registered_calendar_codes() -> Vec<String> {
+    let mut codes: Vec<String> = REGISTRY.read().unwrap().calendars.keys().cloned().collect();
+    codes.sort();
+    codes
+}
@@ -1,11 +1,28 @@
 use chrono::{Datelike, NaiveDate, Weekday, Month, Months, Duration};
+use lazy_static::lazy_static;
+use std::collections::HashSet;
 use std::ops;
+use std::sync::RwLock;
 
-pub trait HolidayRule {
-    fn get_date(&self, year: i32) -> NaiveDate;
+// Rules are Send + Sync so that calendars can be shared between threads (see calendar_registry).
+pub trait HolidayRule: Send + Sync {
+    // Holiday in a year, None if the rule gives none that year (a YearRangeRule out of its range, a tabulated rule outside
+    // its table). For rules giving several dates, the first one.
+    fn get_date(&self, year: i32) -> Option<NaiveDate>;
+    // Holidays the rule gives in a year, sorted and without repetitions. Rules giving several dates override it, and
+    // implement get_date as the first of them.
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        self.get_date(year).into_iter().collect()
+    }
     fn clone_dyn(&self) -> Box<dyn HolidayRule>;
 }
 
+fn sorted_dates(mut dates: Vec<NaiveDate>) -> Vec<NaiveDate> {
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
 impl Clone for Box<dyn HolidayRule> {
     fn clone(&self) -> Self {
         self.clone_dyn()
@@ -23,18 +40,18 @@ impl OrdinalWeekWeekdayRule {
     pub fn new(ordinal: u8, weekday: Weekday, month: Month) -> Self {
         OrdinalWeekWeekdayRule {
             month: month as u32 + 1,
-            weekday: weekday,
-            ordinal: ordinal
+            weekday,
+            ordinal
         }
     }
 }
 impl HolidayRule for OrdinalWeekWeekdayRule {
-    fn get_date(&self, year: i32) -> NaiveDate {
-        let date = NaiveDate::from_ymd_opt(year, self.month, 1).unwrap();
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        let date = NaiveDate::from_ymd_opt(year, self.month, 1)?;
 
-        let days_to_add_to_get_to_weekday = (self.weekday as i64 - date.weekday() as i64)%7;
+        let days_to_add_to_get_to_weekday = (self.weekday as i64 - date.weekday() as i64).rem_euclid(7);
         let first_weekday_occurrence: NaiveDate = date + Duration::days(days_to_add_to_get_to_weekday);
-        first_weekday_occurrence + chrono::Duration::weeks(self.ordinal as i64 - 1)
+        Some(first_weekday_occurrence + chrono::Duration::weeks(self.ordinal as i64 - 1))
     }
 
     fn clone_dyn(&self) -> Box<dyn HolidayRule>{
@@ -43,23 +60,23 @@ impl HolidayRule for OrdinalWeekWeekdayRule {
 }
 
 #[derive(Clone)]
-struct LastWeekWeekdayRule{
-    month: u32,
-    weekday: Weekday
+pub struct LastWeekWeekdayRule{
+    pub month: u32,
+    pub weekday: Weekday
 }
 impl LastWeekWeekdayRule{
     pub fn new(weekday: Weekday, month: Month) -> Self {
         LastWeekWeekdayRule{
             month: month as u32 + 1,
-            weekday: weekday
+            weekday
         }
     }
 }
 impl HolidayRule for LastWeekWeekdayRule {
-    fn get_date(&self, year: i32) -> NaiveDate {
-        let last_day: NaiveDate = (NaiveDate::from_ymd_opt(year, self.month, 1).unwrap() + Months::new(1)).pred_opt().unwrap();
-        let days_to_substract_to_get_to_weekday: i64 = (last_day.weekday() as i64 - self.weekday as i64 )%7;
-        last_day - Duration::days(days_to_substract_to_get_to_weekday)
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        let last_day: NaiveDate = (NaiveDate::from_ymd_opt(year, self.month, 1)? + Months::new(1)).pred_opt()?;
+        let days_to_substract_to_get_to_weekday: i64 = (last_day.weekday() as i64 - self.weekday as i64).rem_euclid(7);
+        Some(last_day - Duration::days(days_to_substract_to_get_to_weekday))
     }
         
     fn clone_dyn(&self) -> Box<dyn HolidayRule>{
@@ -77,13 +94,14 @@ impl MonthDayRule {
     pub fn new(month: Month, day: u8) -> Self {
         MonthDayRule {
             month: month as u32 + 1,
-            day: day
+            day
         }
     }
 }
 impl HolidayRule for MonthDayRule {
-    fn get_date(&self, year: i32) -> NaiveDate {
-        NaiveDate::from_ymd_opt(year, self.month, self.day as u32).unwrap()
+    // None for the 29th of February of non leap years.
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, self.month, self.day as u32)
     }
         
     fn clone_dyn(&self) -> Box<dyn HolidayRule>{
@@ -91,15 +109,31 @@ impl HolidayRule for MonthDayRule {
     }
 }
 
-fn easter_sunday(year: i32) -> NaiveDate {
-    let a = year % 19;
-    let b = year / 100;
-    let c = year % 100;
-    let d = b / 4;
-    let e = b % 4;
-    let f = (b + 8) / 25;
-    let g = (b - f + 1) / 3;
-    let h = (19 * a + b - d - g + 15) % 30;
+// Easter Sunday is computed with the Gregorian (Western churches) or the Julian (Orthodox churches) computus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Computus {
+    Western,
+    Orthodox,
+}
+
+// Easter Sunday as a Gregorian date. Orthodox Easter is computed in the Julian calendar and then converted.
+pub fn easter_sunday(year: i32, computus: Computus) -> NaiveDate {
+    match computus {
+        Computus::Western => western_easter_sunday(year),
+        Computus::Orthodox => orthodox_easter_sunday(year),
+    }
+}
+
+// Euclidean division keeps the computus valid for negative (proleptic) years.
+fn western_easter_sunday(year: i32) -> NaiveDate {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
     let i = c / 4;
     let k = c % 4;
     let l = (32 + 2 * e + 2 * i - h - k) % 7;
@@ -110,140 +144,1055 @@ fn easter_sunday(year: i32) -> NaiveDate {
     NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
 }
 
+fn orthodox_easter_sunday(year: i32) -> NaiveDate {
+    let a = year.rem_euclid(4);
+    let b = year.rem_euclid(7);
+    let c = year.rem_euclid(19);
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let month = (d + e + 114) / 31;
+    let day = ((d + e + 114) % 31) + 1;
+    // Days the Julian calendar is behind the Gregorian one in March and April (13 from 1900 to 2099).
+    let julian_lag = year.div_euclid(100) - year.div_euclid(400) - 2;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap() + Duration::days(julian_lag as i64)
+}
+
+// Holidays a fixed number of days away from Easter Sunday: Good Friday => -2, Easter Monday => 1, Ascension Day => 39,
+// Whit Monday => 50, Corpus Christi => 60, Carnival Tuesday => -47.
 #[derive(Clone)]
-pub struct MondayEasterRule;
-impl HolidayRule for MondayEasterRule {
-    fn get_date(&self, year: i32) -> NaiveDate {
-        let es: NaiveDate = easter_sunday(year);
-        es.succ_opt().unwrap()
+pub struct EasterOffsetRule {
+    pub offset: i64,
+    pub computus: Computus,
+}
+impl EasterOffsetRule {
+    pub fn new(offset: i64) -> Self {
+        EasterOffsetRule::new_with_computus(offset, Computus::Western)
     }
-        
+
+    // Orthodox holidays (Greece, Cyprus, Romania...) use Computus::Orthodox.
+    pub fn new_with_computus(offset: i64, computus: Computus) -> Self {
+        EasterOffsetRule { offset, computus }
+    }
+}
+impl HolidayRule for EasterOffsetRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        Some(easter_sunday(year, self.computus) + Duration::days(self.offset))
+    }
+
     fn clone_dyn(&self) -> Box<dyn HolidayRule>{
         Box::new(self.clone())
     }
 }
 
+// Observance policies move a holiday falling on a Saturday or Sunday to the weekday on which it is observed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Observance {
+    NearestWeekday, // Saturday => Friday, Sunday => Monday (US federal holidays)
+    NextMonday, // Saturday or Sunday => Monday
+    SundayToMonday, // Sunday => Monday, Saturday is not observed on another day
+    SaturdayToFriday, // Saturday => Friday, Sunday is not observed on another day
+}
+impl Observance {
+    pub fn apply(&self, date: NaiveDate) -> NaiveDate {
+        match (self, date.weekday()) {
+            (Observance::NearestWeekday, Weekday::Sat) | (Observance::SaturdayToFriday, Weekday::Sat) => date - Duration::days(1),
+            (Observance::NearestWeekday, Weekday::Sun) | (Observance::SundayToMonday, Weekday::Sun) => date + Duration::days(1),
+            (Observance::NextMonday, Weekday::Sat) => date + Duration::days(2),
+            (Observance::NextMonday, Weekday::Sun) => date + Duration::days(1),
+            _ => date,
+        }
+    }
+}
+
+// Wraps any rule with an observance policy (Independence day => ObservedRule::new(Box::new(MonthDayRule::new(Month::July, 4)), Observance::NearestWeekday))
 #[derive(Clone)]
-pub struct FridayEasterRule;
-impl HolidayRule for FridayEasterRule {
-    fn get_date(&self, year: i32) -> NaiveDate {
-        let es: NaiveDate = easter_sunday(year);
-        es - Duration::days(2)
+pub struct ObservedRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub observance: Observance,
+}
+impl ObservedRule {
+    pub fn new(rule: Box<dyn HolidayRule>, observance: Observance) -> Self {
+        ObservedRule {
+            rule,
+            observance
+        }
     }
-        
+}
+impl HolidayRule for ObservedRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        sorted_dates(self.rule.get_dates(year).into_iter().map(|date| self.observance.apply(date)).collect())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Substitute day chains: a holiday falling on a weekend, or on a day already taken by one of the preceding holidays of the chain,
+// is observed on the next free weekday. UK Christmas and Boxing day:
+//     let christmas = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 25)), vec![]);
+//     let boxing_day = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 26)), vec![Box::new(christmas.clone())]);
+// The days that are substituted can be set with new_with_weekend (Japanese holidays are only substituted when they fall on a Sunday).
+#[derive(Clone)]
+pub struct SubstituteDayRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub preceding: Vec<Box<dyn HolidayRule>>,
+    pub weekend: Vec<Weekday>,
+}
+impl SubstituteDayRule {
+    pub fn new(rule: Box<dyn HolidayRule>, preceding: Vec<Box<dyn HolidayRule>>) -> Self {
+        SubstituteDayRule::new_with_weekend(rule, preceding, &SATURDAY_SUNDAY)
+    }
+
+    pub fn new_with_weekend(rule: Box<dyn HolidayRule>, preceding: Vec<Box<dyn HolidayRule>>, weekend: &[Weekday]) -> Self {
+        SubstituteDayRule {
+            rule,
+            preceding,
+            weekend: weekend.to_vec()
+        }
+    }
+
+    fn substitute(&self, date: NaiveDate, taken: &[NaiveDate]) -> NaiveDate {
+        let mut date: NaiveDate = date;
+        while self.weekend.contains(&date.weekday()) || taken.contains(&date) {
+            date = date.succ_opt().unwrap();
+        }
+        date
+    }
+}
+impl HolidayRule for SubstituteDayRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    // Days of a multi-day holiday (see DaySpanRule) that stay in place are taken too, so a Sunday first day of a three
+    // day Chinese New Year moves after the third day.
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        let preceding: Vec<NaiveDate> = self.preceding.iter().flat_map(|rule| rule.get_dates(year)).collect();
+        let stays = |date: &NaiveDate| !self.weekend.contains(&date.weekday()) && !preceding.contains(date);
+        let dates: Vec<NaiveDate> = self.rule.get_dates(year);
+        let mut taken: Vec<NaiveDate> = dates.iter().copied().filter(stays).chain(preceding.iter().copied()).collect();
+        let mut substituted: Vec<NaiveDate> = Vec::with_capacity(dates.len());
+        for date in dates {
+            if stays(&date) {
+                substituted.push(date);
+            } else {
+                let substitute: NaiveDate = self.substitute(date, &taken);
+                taken.push(substitute);
+                substituted.push(substitute);
+            }
+        }
+        sorted_dates(substituted)
+    }
+
     fn clone_dyn(&self) -> Box<dyn HolidayRule>{
         Box::new(self.clone())
     }
 }
 
+// Moves a holiday a number of days depending on the weekday it falls on (shifts are indexed from Monday). For moving rules
+// Observance does not cover, like the Chilean holidays moved to the closest Monday:
+//     WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::June, 29)), [0, -1, -2, -3, 3, 0, 0])
+#[derive(Clone)]
+pub struct WeekdayShiftRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub shifts: [i64; 7],
+}
+impl WeekdayShiftRule {
+    pub fn new(rule: Box<dyn HolidayRule>, shifts: [i64; 7]) -> Self {
+        WeekdayShiftRule {
+            rule,
+            shifts
+        }
+    }
+
+    fn shift(&self, date: NaiveDate) -> NaiveDate {
+        date + Duration::days(self.shifts[date.weekday().num_days_from_monday() as usize])
+    }
+}
+impl HolidayRule for WeekdayShiftRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        sorted_dates(self.rule.get_dates(year).into_iter().map(|date| self.shift(date)).collect())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Holiday only in the years it falls on one of the given weekdays (Chilean 17th of September is a holiday when it is a Monday)
+#[derive(Clone)]
+pub struct OnWeekdaysRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub weekdays: Vec<Weekday>,
+}
+impl OnWeekdaysRule {
+    pub fn new(rule: Box<dyn HolidayRule>, weekdays: &[Weekday]) -> Self {
+        OnWeekdaysRule {
+            rule,
+            weekdays: weekdays.to_vec()
+        }
+    }
+}
+impl HolidayRule for OnWeekdaysRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        self.rule.get_dates(year).into_iter().filter(|date| self.weekdays.contains(&date.weekday())).collect()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Limits a rule to the years a holiday existed (Japanese Mountain Day => YearRangeRule::new(Box::new(MonthDayRule::new(Month::August, 11)), Some(2016), None))
+#[derive(Clone)]
+pub struct YearRangeRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub start_year: Option<i32>,
+    pub end_year: Option<i32>,
+}
+impl YearRangeRule {
+    pub fn new(rule: Box<dyn HolidayRule>, start_year: Option<i32>, end_year: Option<i32>) -> Self {
+        YearRangeRule {
+            rule,
+            start_year,
+            end_year
+        }
+    }
+}
+impl HolidayRule for YearRangeRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        if self.start_year.is_some_and(|start_year| year < start_year) || self.end_year.is_some_and(|end_year| year > end_year) {
+            return vec![];
+        }
+        self.rule.get_dates(year)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Day between two holidays falling two days apart (Japanese citizens' holiday between Respect for the Aged Day and the autumnal equinox)
+#[derive(Clone)]
+pub struct BridgeDayRule {
+    pub before: Box<dyn HolidayRule>,
+    pub after: Box<dyn HolidayRule>,
+}
+impl BridgeDayRule {
+    pub fn new(before: Box<dyn HolidayRule>, after: Box<dyn HolidayRule>) -> Self {
+        BridgeDayRule {
+            before,
+            after
+        }
+    }
+}
+impl HolidayRule for BridgeDayRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        let after: Vec<NaiveDate> = self.after.get_dates(year);
+        self.before.get_dates(year).into_iter()
+            .filter(|date| after.contains(&(*date + Duration::days(2))))
+            .map(|date| date.succ_opt().unwrap())
+            .collect()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    MarchEquinox,
+    JuneSolstice,
+    SeptemberEquinox,
+    DecemberSolstice,
+}
+
+// Periodic terms (amplitude, phase in degrees, speed in degrees per Julian century) of Meeus, Astronomical Algorithms, table 27.C.
+const SEASON_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96, 1934.136), (203.0, 337.23, 32964.467), (199.0, 342.08, 20.186), (182.0, 27.85, 445267.112),
+    (156.0, 73.14, 45036.886), (136.0, 171.52, 22518.443), (77.0, 222.54, 65928.934), (74.0, 296.72, 3034.906),
+    (70.0, 243.58, 9037.513), (58.0, 119.81, 33718.147), (52.0, 297.17, 150.678), (50.0, 21.02, 2281.226),
+    (45.0, 247.54, 29929.562), (44.0, 325.15, 31555.956), (29.0, 60.93, 4443.417), (18.0, 155.12, 67555.328),
+    (17.0, 288.79, 4562.452), (16.0, 198.04, 62894.029), (14.0, 199.76, 31436.921), (12.0, 95.39, 14577.848),
+    (12.0, 287.11, 31931.756), (12.0, 320.81, 34777.259), (9.0, 227.73, 1222.114), (8.0, 15.45, 16859.074),
+];
+
+// Date of an equinox or solstice at a fixed UTC offset (Japanese Vernal Equinox Day => SeasonRule::new(Season::MarchEquinox, 9)).
+// Uses Meeus, Astronomical Algorithms, chapter 27, accurate to about a minute between years 1000 and 3000.
+#[derive(Clone)]
+pub struct SeasonRule {
+    pub season: Season,
+    pub utc_offset_hours: i32,
+}
+impl SeasonRule {
+    pub fn new(season: Season, utc_offset_hours: i32) -> Self {
+        SeasonRule {
+            season,
+            utc_offset_hours
+        }
+    }
+
+    // Julian ephemeris day of the season start.
+    fn julian_day(&self, year: i32) -> f64 {
+        let y: f64 = (year as f64 - 2000.0) / 1000.0;
+        let coefficients: [f64; 5] = match self.season {
+            Season::MarchEquinox => [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+            Season::JuneSolstice => [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+            Season::SeptemberEquinox => [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+            Season::DecemberSolstice => [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+        };
+        let mean_day: f64 = coefficients.iter().rev().fold(0.0, |value, coefficient| value * y + coefficient);
+        let t: f64 = (mean_day - 2451545.0) / 36525.0;
+        let w: f64 = (35999.373 * t - 2.47).to_radians();
+        let delta_lambda: f64 = 1.0 + 0.0334 * w.cos() + 0.0007 * (2.0 * w).cos();
+        let s: f64 = SEASON_TERMS.iter().map(|(a, b, c)| a * (b + c * t).to_radians().cos()).sum();
+        mean_day + 0.00001 * s / delta_lambda
+    }
+}
+impl HolidayRule for SeasonRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        // Julian day 2440587.5 is 1970-01-01 at 00:00 UTC.
+        let days_from_epoch: f64 = self.julian_day(year) - 2440587.5 + self.utc_offset_hours as f64 / 24.0;
+        Some(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(days_from_epoch.floor() as i64))
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Holidays spanning several consecutive days, starting offset days after the date of rule (Chinese New Year's Eve to the
+// sixth day => DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), -1, 7)).
+#[derive(Clone)]
+pub struct DaySpanRule {
+    pub rule: Box<dyn HolidayRule>,
+    pub offset: i64,
+    pub days: u32,
+}
+impl DaySpanRule {
+    pub fn new(rule: Box<dyn HolidayRule>, offset: i64, days: u32) -> Self {
+        if days == 0 {
+            panic!("A day span must contain at least one day.");
+        }
+        DaySpanRule {
+            rule,
+            offset,
+            days
+        }
+    }
+}
+impl HolidayRule for DaySpanRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    // Includes the days of spans starting in the previous or the next year that fall in year.
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        sorted_dates((year - 1..=year + 1)
+            .flat_map(|y| self.rule.get_dates(y))
+            .flat_map(|date| (0..self.days as i64).map(move |day| date + Duration::days(self.offset + day)))
+            .filter(|date| date.year() == year)
+            .collect())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Holidays on announced dates that no rule gives (Singapore's Vesak Day or Deepavali). Years without a date have no holiday.
+#[derive(Clone)]
+pub struct TabulatedRule {
+    pub dates: Vec<NaiveDate>,
+}
+impl TabulatedRule {
+    pub fn new(dates: Vec<NaiveDate>) -> Self {
+        TabulatedRule {
+            dates
+        }
+    }
+}
+impl HolidayRule for TabulatedRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        sorted_dates(self.dates.iter().copied().filter(|date| date.year() == year).collect())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+// Date the apparent longitude of the Sun reaches a multiple of 15 degrees, at a fixed UTC offset (Qingming => SolarTermRule::new(15, 8)).
+// Uses the solar coordinates of Meeus, Astronomical Algorithms, chapter 25, accurate to about 15 minutes.
+#[derive(Clone)]
+pub struct SolarTermRule {
+    pub longitude: u32,
+    pub utc_offset_hours: i32,
+}
+impl SolarTermRule {
+    pub fn new(longitude: u32, utc_offset_hours: i32) -> Self {
+        if longitude >= 360 || !longitude.is_multiple_of(15) {
+            panic!("Solar term longitude must be a multiple of 15 degrees smaller than 360.");
+        }
+        SolarTermRule {
+            longitude,
+            utc_offset_hours
+        }
+    }
+
+    // Apparent longitude of the Sun in degrees.
+    fn solar_longitude(julian_day: f64) -> f64 {
+        let t: f64 = (julian_day - 2451545.0) / 36525.0;
+        let mean_longitude: f64 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+        let mean_anomaly: f64 = (357.52911 + 35999.05029 * t - 0.0001537 * t * t).to_radians();
+        let center: f64 = (1.914602 - 0.004817 * t - 0.000014 * t * t) * mean_anomaly.sin()
+            + (0.019993 - 0.000101 * t) * (2.0 * mean_anomaly).sin()
+            + 0.000289 * (3.0 * mean_anomaly).sin();
+        let omega: f64 = (125.04 - 1934.136 * t).to_radians();
+        (mean_longitude + center - 0.00569 - 0.00478 * omega.sin()).rem_euclid(360.0)
+    }
+
+    // Julian day of the solar term. Longitudes from 285 degrees are reached in January to March, before the March equinox.
+    fn julian_day(&self, year: i32) -> f64 {
+        let longitude: f64 = self.longitude as f64;
+        let years_from_equinox: f64 = if self.longitude >= 285 { longitude / 360.0 - 1.0 } else { longitude / 360.0 };
+        let mut julian_day: f64 = 2451623.80984 + 365.242189 * (year as f64 - 2000.0 + years_from_equinox);
+        for _ in 0..5 {
+            let difference: f64 = (longitude - Self::solar_longitude(julian_day) + 540.0).rem_euclid(360.0) - 180.0;
+            julian_day += difference * 365.242189 / 360.0;
+        }
+        julian_day
+    }
+}
+impl HolidayRule for SolarTermRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        let days_from_epoch: f64 = self.julian_day(year) - 2440587.5 + self.utc_offset_hours as f64 / 24.0;
+        Some(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(days_from_epoch.floor() as i64))
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LunarCalendar {
+    Chinese, // Lunisolar calendar of GB/T 33661-2017, lunar years 1912 to 2100
+    Hijri, // Umm al-Qura calendar of Saudi Arabia, years 1330 to 1524 AH (1911 to 2101)
+}
+
+// Bits 0 to 12 flag the 30 day months of the year, in order. Bits 13 to 16 are the position of the leap month (0 if there
+// is none), e.g. 3 when the second month is followed by a leap second month.
+const CHINESE_FIRST_YEAR: (i32, u32, u32) = (1912, 2, 18);
+const CHINESE_YEARS: [u32; 189] = [
+    0x00b25, 0x00a4b, 0x0d4ab, 0x002ad, 0x0056b, 0x06b69, 0x00da9, 0x11d92, 0x00e92, 0x00d25, // 1912
+    0x0da4d, 0x00a56, 0x002b6, 0x0b5b5, 0x006d4, 0x00ea9, 0x07e92, 0x00e92, 0x0ed26, 0x0052b, // 1922
+    0x00a57, 0x0d2b6, 0x00b5a, 0x006d4, 0x08ec9, 0x00749, 0x11693, 0x00a93, 0x0052b, 0x0ea5b, // 1932
+    0x00aad, 0x0056a, 0x0bb55, 0x00ba4, 0x00b49, 0x07a93, 0x00a95, 0x1152d, 0x00536, 0x00aad, // 1942
+    0x0d5aa, 0x005b2, 0x00da5, 0x09d4a, 0x00d4a, 0x12a95, 0x00a97, 0x00556, 0x0eab5, 0x00ad5, // 1952
+    0x006d2, 0x0aea5, 0x00ea5, 0x0064a, 0x08c97, 0x00a9b, 0x1155a, 0x0056a, 0x00b69, 0x0d752, // 1962
+    0x00b52, 0x00b25, 0x0b64b, 0x00a4b, 0x134ab, 0x002ad, 0x0056d, 0x0eb69, 0x00da9, 0x00d92, // 1972
+    0x0bd25, 0x00d25, 0x17a4d, 0x00a56, 0x002b6, 0x0e5b5, 0x006d5, 0x00ea9, 0x0de92, 0x00e92, // 1982
+    0x00d26, 0x08a56, 0x00a57, 0x134d6, 0x0035a, 0x006d5, 0x0d6c9, 0x00749, 0x00693, 0x0b52b, // 1992
+    0x0052b, 0x00a5b, 0x0755a, 0x0056a, 0x11b55, 0x00ba4, 0x00b49, 0x0da93, 0x00a95, 0x0052d, // 2002
+    0x0aaad, 0x00ab5, 0x155aa, 0x005d2, 0x00da5, 0x0fd4a, 0x00d4a, 0x00c95, 0x0b52e, 0x00556, // 2012
+    0x00ab5, 0x075b2, 0x006d2, 0x0eea5, 0x00725, 0x0064b, 0x0cc97, 0x00cab, 0x0055a, 0x08ad6, // 2022
+    0x00b69, 0x19752, 0x00b52, 0x00b25, 0x0fa4b, 0x00a4b, 0x004ab, 0x0c55b, 0x005ad, 0x00b6a, // 2032
+    0x07b52, 0x00d92, 0x11d25, 0x00d25, 0x00a55, 0x0d4ad, 0x004b6, 0x005b5, 0x08daa, 0x00ec9, // 2042
+    0x13e92, 0x00e92, 0x00d26, 0x0ea56, 0x00a57, 0x00556, 0x0a6d5, 0x00755, 0x00749, 0x08e93, // 2052
+    0x00693, 0x1152b, 0x0052b, 0x00a5b, 0x0d55a, 0x0056a, 0x00b65, 0x0b74a, 0x00b4a, 0x13a95, // 2062
+    0x00a95, 0x0052d, 0x0eaad, 0x00ab5, 0x005aa, 0x0aba5, 0x00da5, 0x00d4a, 0x09c95, 0x00c96, // 2072
+    0x1194e, 0x00556, 0x00ab5, 0x0d5b2, 0x006d2, 0x00ea5, 0x0ae4a, 0x0068b, 0x12c97, 0x004ab, // 2082
+    0x0055b, 0x0ead6, 0x00b6a, 0x00752, 0x0b725, 0x00b45, 0x00a8b, 0x0749b, 0x004ab, // 2092
+];
+
+// Bits 0 to 11 flag the 30 day months of the year.
+const HIJRI_FIRST_YEAR: (i32, u32, u32) = (1911, 12, 22);
+const HIJRI_YEARS: [u16; 195] = [
+    0x64b, 0xa9b, 0x35a, 0x6d9, 0x5d4, 0xda5, 0xd4a, 0xa95, 0x536, 0x975, 0x2f4, 0x6e9, // 1330
+    0x6d4, 0x6a9, 0x535, 0x25d, 0x4bd, 0x9ba, 0x3b4, 0xb69, 0xb2a, 0xa55, 0x4ad, 0xa5d, // 1342
+    0x2da, 0x6d9, 0xeaa, 0xe94, 0xd2a, 0xc56, 0x4ae, 0xa6d, 0x56a, 0xd55, 0xd4a, 0xa93, // 1354
+    0x52b, 0xa5b, 0x53a, 0x6b5, 0xea9, 0xd52, 0xd29, 0xa55, 0x4ad, 0x56d, 0xaea, 0x6e4, // 1366
+    0xed1, 0xda2, 0xaaa, 0x95a, 0x2da, 0x5b9, 0xbb2, 0x764, 0x6c9, 0x555, 0x2ab, 0x4db, // 1378
+    0xaba, 0x5b4, 0xda9, 0xd52, 0xaa5, 0x92d, 0x26d, 0x8ed, 0x2da, 0xad5, 0xaa5, 0xa4b, // 1390
+    0x497, 0x937, 0x2b6, 0x975, 0xd69, 0xd52, 0xc95, 0x92b, 0x25b, 0x4db, 0x9d5, 0x5d2, // 1402
+    0xda5, 0xd4a, 0xa95, 0x54d, 0xaad, 0x3aa, 0xbd2, 0xbc4, 0xb89, 0xa95, 0x52d, 0x5ad, // 1414
+    0xb6a, 0x6d4, 0xdc9, 0xd92, 0xaa6, 0x956, 0x2ae, 0x56d, 0x36a, 0xb55, 0xaaa, 0x94d, // 1426
+    0x49d, 0x95d, 0x2ba, 0x5b5, 0x5aa, 0xd55, 0xa9a, 0x92e, 0x26e, 0x55d, 0xada, 0x6d4, // 1438
+    0x6a5, 0xb27, 0xa4d, 0x4ad, 0x56d, 0xb5a, 0x754, 0xf49, 0xe92, 0xd26, 0xa56, 0x356, // 1450
+    0x6b5, 0xbaa, 0xb92, 0xb25, 0x68b, 0xa9b, 0x55a, 0xada, 0x5b4, 0xda9, 0xb52, 0xa9a, // 1462
+    0x536, 0x276, 0x575, 0xaf2, 0x6d4, 0x6a9, 0x555, 0x2ad, 0x4bd, 0x9ba, 0x574, 0xb69, // 1474
+    0xb52, 0xa95, 0x52d, 0xa5d, 0x4da, 0xad9, 0x6b2, 0xe95, 0xe2a, 0xc96, 0x92e, 0xaad, // 1486
+    0x56a, 0xd65, 0xd4a, 0xd15, 0x62b, 0xc5b, 0x53a, 0x6b5, 0xdb2, 0xd64, 0xd29, 0xa55, // 1498
+    0x4ad, 0x96d, 0xaea, 0x6e8, 0xed1, 0xda4, 0xd4a, 0xa6a, 0x2da, 0x5b9, 0xb72, 0xb68, // 1510
+    0x6d1, 0x655, 0x4ab, // 1522
+];
+
+struct LunarYear {
+    start: NaiveDate,
+    month_lengths: Vec<u32>,
+    leap_month: usize,
+}
+
+fn lunar_years(first_year: (i32, u32, u32), years: &[u32], has_leap_months: bool) -> Vec<LunarYear> {
+    let mut start: NaiveDate = NaiveDate::from_ymd_opt(first_year.0, first_year.1, first_year.2).unwrap();
+    years.iter().map(|&year| {
+        let leap_month: usize = if has_leap_months { (year >> 13) as usize } else { 0 };
+        let n_months: usize = if leap_month > 0 { 13 } else { 12 };
+        let month_lengths: Vec<u32> = (0..n_months).map(|i| 29 + (year >> i & 1)).collect();
+        let lunar_year = LunarYear { start, month_lengths, leap_month };
+        start += Duration::days(lunar_year.month_lengths.iter().sum::<u32>() as i64);
+        lunar_year
+    }).collect()
+}
+
+lazy_static! {
+    static ref CHINESE_LUNAR_YEARS: Vec<LunarYear> = lunar_years(CHINESE_FIRST_YEAR, &CHINESE_YEARS, true);
+    static ref HIJRI_LUNAR_YEARS: Vec<LunarYear> = lunar_years(HIJRI_FIRST_YEAR, &HIJRI_YEARS.map(u32::from), false);
+}
+
+impl LunarCalendar {
+    fn years(self) -> &'static [LunarYear] {
+        match self {
+            LunarCalendar::Chinese => &CHINESE_LUNAR_YEARS,
+            LunarCalendar::Hijri => &HIJRI_LUNAR_YEARS,
+        }
+    }
+}
+
+// Holiday on a day of a lunar month (Mid-Autumn Festival => LunarDateRule::new(LunarCalendar::Chinese, 8, 15), Eid al-Adha
+// => LunarDateRule::new(LunarCalendar::Hijri, 12, 10)). Chinese leap months are skipped, so month is the regular month.
+// Outside the tabulated years there is no holiday, and a Hijri date can fall twice in a Gregorian year.
+#[derive(Clone)]
+pub struct LunarDateRule {
+    pub calendar: LunarCalendar,
+    pub month: u32,
+    pub day: u32,
+}
+impl LunarDateRule {
+    pub fn new(calendar: LunarCalendar, month: u32, day: u32) -> Self {
+        if !(1..=12).contains(&month) || !(1..=30).contains(&day) {
+            panic!("Lunar month must be between 1 and 12 and day between 1 and 30.");
+        }
+        LunarDateRule {
+            calendar,
+            month,
+            day
+        }
+    }
+}
+impl HolidayRule for LunarDateRule {
+    fn get_date(&self, year: i32) -> Option<NaiveDate> {
+        self.get_dates(year).first().copied()
+    }
+
+    fn get_dates(&self, year: i32) -> Vec<NaiveDate> {
+        let years: &[LunarYear] = self.calendar.years();
+        let first: usize = years.partition_point(|lunar_year| lunar_year.start.year() < year - 1);
+        years[first..].iter()
+            .take_while(|lunar_year| lunar_year.start.year() <= year)
+            .filter_map(|lunar_year| {
+                // Months from the leap month on are one position later.
+                let leap_month: usize = lunar_year.leap_month;
+                let position: usize = if leap_month > 0 && self.month as usize >= leap_month { self.month as usize } else { self.month as usize - 1 };
+                if self.day > lunar_year.month_lengths[position] {
+                    return None;
+                }
+                let days: u32 = lunar_year.month_lengths[..position].iter().sum::<u32>() + self.day - 1;
+                Some(lunar_year.start + Duration::days(days as i64))
+            })
+            .filter(|date| date.year() == year)
+            .collect()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn HolidayRule>{
+        Box::new(self.clone())
+    }
+}
+
+pub const SATURDAY_SUNDAY: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+pub const FRIDAY_SATURDAY: [Weekday; 2] = [Weekday::Fri, Weekday::Sat];
+
+// Years covered by the business day index when it is first built around a queried date, and minimum years added on each extension.
+const INDEX_YEARS_BEFORE: i32 = 5;
+const INDEX_YEARS_AFTER: i32 = 55;
+const INDEX_YEARS_EXTENSION: i32 = 10;
+// Rules are evaluated up to two years around the indexed ones (observed dates and spans crossing a year end), so the
+// index stops that many years before chrono's first and last years.
+const INDEX_YEARS_MARGIN: i32 = 2;
+
+fn clamp_index_year(year: i32) -> i32 {
+    year.clamp(NaiveDate::MIN.year() + INDEX_YEARS_MARGIN, NaiveDate::MAX.year() - INDEX_YEARS_MARGIN)
+}
+
+// Business day ordinal table over [start, end]. Gives constant time business day arithmetic.
+struct BusinessDayIndex {
+    start: NaiveDate,
+    start_year: i32,
+    end_year: i32,
+    // cumulative_count[i] = number of business days in [start, start + i days)
+    cumulative_count: Vec<u32>,
+    // business_days[k] = days from start to the k-th business day
+    business_days: Vec<u32>,
+}
+impl BusinessDayIndex {
+    fn new(calendar: &Calendar, start_year: i32, end_year: i32) -> Self {
+        let (start_year, end_year) = (clamp_index_year(start_year), clamp_index_year(end_year));
+        let start: NaiveDate = NaiveDate::from_ymd_opt(start_year, 1, 1).unwrap();
+        let end: NaiveDate = NaiveDate::from_ymd_opt(end_year, 12, 31).unwrap();
+
+        // Rules are evaluated once per year instead of once per day. Neighbouring years are included for observed dates
+        // that cross a year end (a Saturday 1st of January observed on Friday 31st of December).
+        let rule_holidays: HashSet<NaiveDate> = (start_year - 1..=end_year + 1)
+            .flat_map(|y| calendar.holiday_rules.iter().flat_map(move |rule| rule.get_dates(y)))
+            .filter(|d| *d >= start && *d <= end)
+            .collect();
+
+        let n_days: usize = (end - start).num_days() as usize + 1;
+        let composed_closed: Vec<bool> = match &calendar.composition {
+            Some(composition) => composition.closed_days(start_year, end_year),
+            None => Vec::new(),
+        };
+        let mut cumulative_count: Vec<u32> = Vec::with_capacity(n_days + 1);
+        let mut business_days: Vec<u32> = Vec::with_capacity(n_days);
+        cumulative_count.push(0);
+        for (i, date) in start.iter_days().take(n_days).enumerate() {
+            let is_holiday: bool = !calendar.removed_holidays.contains(&date)
+                && (calendar.holidays.contains(&date) || rule_holidays.contains(&date)
+                    || (!composed_closed.is_empty() && composed_closed[i] && !calendar.is_weekend(date)));
+            if !calendar.is_weekend(date) && !is_holiday {
+                business_days.push(i as u32);
+            }
+            cumulative_count.push(business_days.len() as u32);
+        }
+        BusinessDayIndex { start, start_year, end_year, cumulative_count, business_days }
+    }
+
+    fn offset(&self, date: NaiveDate) -> Option<usize> {
+        if date.year() < self.start_year || date.year() > self.end_year {
+            return None;
+        }
+        Some((date - self.start).num_days() as usize)
+    }
+
+    fn is_business_day(&self, offset: usize) -> bool {
+        self.cumulative_count[offset + 1] > self.cumulative_count[offset]
+    }
+
+    fn date(&self, business_day: usize) -> NaiveDate {
+        self.start + Duration::days(self.business_days[business_day] as i64)
+    }
+
+    fn add_business_days(&self, t: NaiveDate, business_days: u32) -> Option<NaiveDate> {
+        let i: usize = self.offset(t)?;
+        let k: usize = (self.cumulative_count[i + 1] + business_days) as usize - 1;
+        if k >= self.business_days.len() {
+            return None;
+        }
+        Some(self.date(k))
+    }
+
+    fn substract_business_days(&self, t: NaiveDate, business_days: u32) -> Option<NaiveDate> {
+        let i: usize = self.offset(t)?;
+        let preceding: u32 = self.cumulative_count[i];
+        if preceding < business_days {
+            return None;
+        }
+        Some(self.date((preceding - business_days) as usize))
+    }
+
+    // Number of business days in [start_date, end_date).
+    fn business_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> Option<i64> {
+        let i: usize = self.offset(start_date)?;
+        let j: usize = self.offset(end_date)?;
+        Some(self.cumulative_count[j] as i64 - self.cumulative_count[i] as i64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CalendarOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+// Calendar built from two others. The weekend of the result is set from the operation, so a day closed by the
+// composition is a holiday only if it is not a weekend day of the result.
+#[derive(Clone)]
+struct Composition {
+    operation: CalendarOperation,
+    left: Calendar,
+    right: Calendar,
+}
+impl Composition {
+    // Difference weekends are the ones of left, so a closed left day that is not a weekend day of the result is a left holiday.
+    fn closes(&self, left_closed: bool, right_closed: bool) -> bool {
+        match self.operation {
+            CalendarOperation::Union => left_closed || right_closed,
+            CalendarOperation::Intersection => left_closed && right_closed,
+            CalendarOperation::Difference => left_closed && !right_closed,
+        }
+    }
+
+    fn is_closed(&self, date: NaiveDate) -> bool {
+        self.closes(!self.left.is_business_day(date), !self.right.is_business_day(date))
+    }
+
+    // is_closed for every day of [start_year, end_year], using business day indexes of both calendars.
+    fn closed_days(&self, start_year: i32, end_year: i32) -> Vec<bool> {
+        let left: BusinessDayIndex = BusinessDayIndex::new(&self.left, start_year, end_year);
+        let right: BusinessDayIndex = BusinessDayIndex::new(&self.right, start_year, end_year);
+        (0..left.cumulative_count.len() - 1)
+            .map(|i| self.closes(!left.is_business_day(i), !right.is_business_day(i)))
+            .collect()
+    }
+}
+
+// A date is a holiday if it was removed explicitly => false, else if it was added explicitly => true, else if any rule generates it => true,
+// else if the calendar is a composition (see intersect and difference) and it closes that day => true.
+// Business day arithmetic uses an index that is built lazily, extended when a date outside of it is queried and dropped whenever the calendar changes.
 pub struct Calendar {
-    pub holidays: Vec<NaiveDate>,
-    pub holiday_rules: Vec<Box<dyn HolidayRule>>
+    holiday_rules: Vec<Box<dyn HolidayRule>>,
+    holidays: HashSet<NaiveDate>,
+    removed_holidays: HashSet<NaiveDate>,
+    weekend: HashSet<Weekday>,
+    composition: Option<Box<Composition>>,
+    business_day_index: RwLock<Option<BusinessDayIndex>>
 }
 impl Calendar {
     pub fn new(holiday_rules: Option<Vec<Box<dyn HolidayRule>>>, holidays: Option<Vec<NaiveDate>>) -> Self {
-        let mut h_vec: Vec<NaiveDate> = holidays.unwrap_or_default();
-        h_vec.sort();
-        h_vec.dedup();
-        Calendar{
-            holidays: h_vec, 
-            holiday_rules: holiday_rules.unwrap_or_default()
+        Calendar::new_with_weekend(holiday_rules, holidays, &SATURDAY_SUNDAY)
+    }
+
+    // Weekend can be any set of weekdays (e.g. FRIDAY_SATURDAY for Israel), but at least one day of the week must be a business day.
+    pub fn new_with_weekend(holiday_rules: Option<Vec<Box<dyn HolidayRule>>>, holidays: Option<Vec<NaiveDate>>, weekend: &[Weekday]) -> Self {
+        let mut calendar = Calendar{
+            holiday_rules: holiday_rules.unwrap_or_default(),
+            holidays: holidays.unwrap_or_default().into_iter().collect(),
+            removed_holidays: HashSet::new(),
+            weekend: HashSet::new(),
+            composition: None,
+            business_day_index: RwLock::new(None)
+        };
+        calendar.set_weekend(weekend);
+        calendar
+    }
+
+    pub fn set_weekend(&mut self, weekend: &[Weekday]) {
+        let weekend: HashSet<Weekday> = weekend.iter().copied().collect();
+        if weekend.len() == 7 {
+            panic!("Weekend can not contain every day of the week.");
         }
+        self.weekend = weekend;
+        self.invalidate_business_day_index();
+    }
+
+    pub fn get_weekend(&self) -> Vec<Weekday> {
+        let mut weekend: Vec<Weekday> = self.weekend.iter().copied().collect();
+        weekend.sort_by_key(|wd| wd.num_days_from_monday());
+        weekend
+    }
+
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
     }
 
+    pub fn get_holiday_rules(&self) -> &[Box<dyn HolidayRule>] {
+        &self.holiday_rules
+    }
+
+    pub fn add_holiday_rule(&mut self, rule: Box<dyn HolidayRule>) {
+        self.holiday_rules.push(rule);
+        self.invalidate_business_day_index();
+    }
+
+    // Deletes explicit holidays and removals. Holiday rules are kept.
     pub fn delete_holidays(&mut self) {
         self.holidays.clear();
+        self.removed_holidays.clear();
+        self.invalidate_business_day_index();
     }
 
+    // Materializes rule dates as explicit holidays.
     pub fn add_holidays_with_rules(&mut self, start_year: i32, end_year: i32) {
         let mut year = start_year;
         while year <= end_year {
             for rule in &self.holiday_rules {
-                self.holidays.push(rule.get_date(year));
+                for holiday in rule.get_dates(year) {
+                    if !self.removed_holidays.contains(&holiday) {
+                        self.holidays.insert(holiday);
+                    }
+                }
             }
             year += 1;
         }
-        self.fix_holidays();
+        self.invalidate_business_day_index();
     }
     
     pub fn add_holidays_from_vec(&mut self, holidays_vec: Vec<NaiveDate>) {
-        self.holidays.extend(holidays_vec);
-        self.fix_holidays();
+        for holiday in holidays_vec {
+            self.add_holiday(holiday);
+        }
     }
 
     pub fn add_holiday(&mut self, holiday: NaiveDate) {
-        self.holidays.push(holiday);
-        self.fix_holidays();
+        self.removed_holidays.remove(&holiday);
+        self.holidays.insert(holiday);
+        self.invalidate_business_day_index();
     }
 
-    fn fix_holidays(&mut self) {
-        self.holidays.sort();
-        self.holidays.dedup();
+    // Marks a date as a business day even if a rule generates it (e.g. the exchange stayed open on a holiday).
+    pub fn remove_holiday(&mut self, holiday: NaiveDate) {
+        self.holidays.remove(&holiday);
+        self.removed_holidays.insert(holiday);
+        self.invalidate_business_day_index();
     }
 
-    pub fn is_holiday_from_inner_vec(&self, date: NaiveDate) -> bool {
+    pub fn remove_holidays_from_vec(&mut self, holidays_vec: Vec<NaiveDate>) {
+        for holiday in holidays_vec {
+            self.remove_holiday(holiday);
+        }
+    }
+
+    // Explicit holidays, sorted.
+    pub fn get_holidays(&self) -> Vec<NaiveDate> {
+        let mut holidays: Vec<NaiveDate> = self.holidays.iter().copied().collect();
+        holidays.sort();
+        holidays
+    }
+
+    // Explicit removals, sorted.
+    pub fn get_removed_holidays(&self) -> Vec<NaiveDate> {
+        let mut removed: Vec<NaiveDate> = self.removed_holidays.iter().copied().collect();
+        removed.sort();
+        removed
+    }
+
+    pub fn is_explicit_holiday(&self, date: NaiveDate) -> bool {
         self.holidays.contains(&date)
     }
 
-    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+    #[deprecated(note = "use is_explicit_holiday instead")]
+    pub fn is_holiday_from_inner_vec(&self, date: NaiveDate) -> bool {
+        self.is_explicit_holiday(date)
+    }
+
+    pub fn is_removed_holiday(&self, date: NaiveDate) -> bool {
+        self.removed_holidays.contains(&date)
+    }
+
+    // Rules of the previous and next years are checked too, for observed dates that cross a year end.
+    pub fn is_rule_holiday(&self, date: NaiveDate) -> bool {
         let y: i32 = date.year();
-        for rule in &self.holiday_rules {
-            if date==rule.get_date(y) {
-                return true;
-            }
+        (y - 1..=y + 1).any(|year| self.holiday_rules.iter().any(|rule| rule.get_dates(year).contains(&date)))
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        if self.removed_holidays.contains(&date) {
+            return false;
         }
-        false
+        self.holidays.contains(&date) || self.is_rule_holiday(date) || self.is_composed_holiday(date)
     }
 
-    fn add_business_day(&self, t: NaiveDate) -> NaiveDate {
-        let mut following: NaiveDate = t + Duration::days(2*((t.weekday() == Weekday::Fri) as i64) + ((t.weekday() == Weekday::Sat) as i64) + 1);
-        while self.is_holiday(following) {
-            following = following.succ_opt().unwrap();
+    fn is_composed_holiday(&self, date: NaiveDate) -> bool {
+        match &self.composition {
+            Some(composition) => !self.is_weekend(date) && composition.is_closed(date),
+            None => false,
         }
-        following
     }
 
     pub fn add_business_days(&self, t: NaiveDate, business_days: u32) -> NaiveDate {
-        let mut added_days: u32 = 0;
-        let mut result_date: NaiveDate = t;
-        while added_days < business_days {
-            result_date = self.add_business_day(result_date);
-            added_days+=1;
+        self.checked_add_business_days(t, business_days)
+            .unwrap_or_else(|| panic!("Adding {} business days to {} gives a date out of range.", business_days, t))
+    }
+
+    pub fn substract_business_days(&self, t: NaiveDate, business_days: u32) -> NaiveDate {
+        self.checked_substract_business_days(t, business_days)
+            .unwrap_or_else(|| panic!("Substracting {} business days from {} gives a date out of range.", business_days, t))
+    }
+
+    // None if the result is out of the business day index range, that stops two years before chrono's limits.
+    pub fn checked_add_business_days(&self, t: NaiveDate, business_days: u32) -> Option<NaiveDate> {
+        if business_days == 0 {
+            return Some(t);
+        }
+        // Each business day is at least a day, so that requests beyond chrono's range are rejected before indexing it.
+        t.checked_add_signed(Duration::days(business_days as i64))?;
+        loop {
+            if let Some(date) = self.with_business_day_index(|index| index.add_business_days(t, business_days)) {
+                return Some(date);
+            }
+            if !self.extend_business_day_index(t, business_days, true) {
+                return None;
+            }
         }
-        result_date
     }
 
-    fn substract_business_day(&self, t: NaiveDate) -> NaiveDate {
-        let mut preceding: NaiveDate = t - Duration::days(2*((t.weekday() == Weekday::Mon) as i64) + ((t.weekday() == Weekday::Sun) as i64) + 1);
-        while self.is_holiday(preceding) {
-            preceding = preceding.pred_opt().unwrap();
+    // None if the result is out of the business day index range, that stops two years before chrono's limits.
+    pub fn checked_substract_business_days(&self, t: NaiveDate, business_days: u32) -> Option<NaiveDate> {
+        if business_days == 0 {
+            return Some(t);
+        }
+        t.checked_sub_signed(Duration::days(business_days as i64))?;
+        loop {
+            if let Some(date) = self.with_business_day_index(|index| index.substract_business_days(t, business_days)) {
+                return Some(date);
+            }
+            if !self.extend_business_day_index(t, business_days, false) {
+                return None;
+            }
         }
-        preceding
     }
 
-    pub fn substract_business_days(&self, t: NaiveDate, business_days: u32) -> NaiveDate {
-        let mut substracted_days: u32 = 0;
-        let mut result_date: NaiveDate = t;
-        while substracted_days < business_days {
-            result_date = self.substract_business_day(result_date);
-            substracted_days+=1;
+    // Number of business days in [start_date, end_date), negative if end_date is before start_date.
+    pub fn business_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        loop {
+            if let Some(count) = self.with_business_day_index(|index| index.business_days_between(start_date, end_date)) {
+                return count;
+            }
+            let mut extended: bool = false;
+            for (date, forward) in [(start_date.min(end_date), false), (start_date.max(end_date), true)] {
+                if self.with_business_day_index(|index| index.offset(date)).is_none() {
+                    extended |= self.extend_business_day_index(date, 0, forward);
+                }
+            }
+            if !extended {
+                panic!("Business days between {} and {} are out of the supported date range.", start_date, end_date);
+            }
+        }
+    }
+
+    // Builds the business day index for [start_year, end_year] ahead of time, so that no query inside that range needs to extend it.
+    pub fn build_business_day_index(&self, start_year: i32, end_year: i32) {
+        if start_year > end_year {
+            panic!("start_year must be smaller or equal to end_year.");
+        }
+        *self.business_day_index.write().unwrap() = Some(BusinessDayIndex::new(self, start_year, end_year));
+    }
+
+    fn with_business_day_index<T>(&self, f: impl Fn(&BusinessDayIndex) -> Option<T>) -> Option<T> {
+        self.business_day_index.read().unwrap().as_ref().and_then(f)
+    }
+
+    // Rebuilds the index so that it covers t and at least business_days / 200 more years after (forward) or before t.
+    // If the index already covers that range, it is extended anyway in the requested direction, as the
+    // calendar might have fewer business days per year than expected. Returns false if the index could not grow, as it
+    // already reaches chrono's limits.
+    fn extend_business_day_index(&self, t: NaiveDate, business_days: u32, forward: bool) -> bool {
+        let years: i32 = INDEX_YEARS_EXTENSION.max((business_days / 200) as i32 + 1);
+        let (start_year, end_year) = if forward { (t.year(), t.year() + years) } else { (t.year() - years, t.year()) };
+
+        let mut index = self.business_day_index.write().unwrap();
+        let (sy, ey) = match index.as_ref() {
+            Some(current) if forward => (current.start_year.min(start_year), end_year.max(current.end_year + years)),
+            Some(current) => (start_year.min(current.start_year - years), current.end_year.max(end_year)),
+            None => (start_year.min(t.year() - INDEX_YEARS_BEFORE), end_year.max(t.year() + INDEX_YEARS_AFTER)),
+        };
+        let (sy, ey) = (clamp_index_year(sy), clamp_index_year(ey));
+        if index.as_ref().is_some_and(|current| current.start_year == sy && current.end_year == ey) {
+            return false;
+        }
+        *index = Some(BusinessDayIndex::new(self, sy, ey));
+        true
+    }
+
+    fn invalidate_business_day_index(&mut self) {
+        *self.business_day_index.get_mut().unwrap() = None;
+    }
+}
+
+// Clones start without a business day index, it is rebuilt on first use.
+impl Clone for Calendar {
+    fn clone(&self) -> Self {
+        Calendar {
+            holiday_rules: self.holiday_rules.clone(),
+            holidays: self.holidays.clone(),
+            removed_holidays: self.removed_holidays.clone(),
+            weekend: self.weekend.clone(),
+            composition: self.composition.clone(),
+            business_day_index: RwLock::new(None)
         }
-        result_date
     }
 }
 
 impl Calendar {
+    // Union: closed if either calendar is closed. Rules, holidays and removals are merged, unless one of the calendars is
+    // itself a composition.
     pub fn combine(&self, other: Calendar) -> Calendar {
-        let mut combined_holidays: Vec<NaiveDate> = self.holidays.clone();
-        combined_holidays.extend(other.holidays.clone().iter());
+        if self.composition.is_some() || other.composition.is_some() {
+            let weekend: Vec<Weekday> = self.weekend.union(&other.weekend).copied().collect();
+            return Calendar::compose(self.clone(), other, CalendarOperation::Union, &weekend);
+        }
+        let combined_holidays: Vec<NaiveDate> = self.holidays.union(&other.holidays).copied().collect();
+
+        // A removal survives only if the other calendar is open that day, otherwise the combined calendar is closed.
+        let combined_removals: Vec<NaiveDate> = self.removed_holidays.iter().filter(|d| !other.is_holiday(**d))
+            .chain(other.removed_holidays.iter().filter(|d| !self.is_holiday(**d)))
+            .copied().collect();
 
         let mut combined_rules: Vec<Box<dyn HolidayRule>>  = self.holiday_rules.clone();
         combined_rules.extend(other.holiday_rules);
 
-        Calendar::new(Some(combined_rules), Some(combined_holidays))
+        let combined_weekend: Vec<Weekday> = self.weekend.union(&other.weekend).copied().collect();
+
+        let mut combined = Calendar::new_with_weekend(Some(combined_rules), Some(combined_holidays), &combined_weekend);
+        combined.remove_holidays_from_vec(combined_removals);
+        combined
+    }
+
+    // Intersection: open if either calendar is open. Weekend days are the ones shared by both calendars.
+    pub fn intersect(&self, other: Calendar) -> Calendar {
+        let weekend: Vec<Weekday> = self.weekend.intersection(&other.weekend).copied().collect();
+        Calendar::compose(self.clone(), other, CalendarOperation::Intersection, &weekend)
+    }
+
+    // Difference: holidays of this calendar that are not holidays of other. A day closed in both calendars is a business
+    // day of the difference. The weekend is the one of this calendar.
+    pub fn difference(&self, other: Calendar) -> Calendar {
+        let weekend: Vec<Weekday> = self.get_weekend();
+        Calendar::compose(self.clone(), other, CalendarOperation::Difference, &weekend)
+    }
+
+    // The result has no rules or explicit holidays of its own; they can be added afterwards as in any other calendar.
+    fn compose(left: Calendar, right: Calendar, operation: CalendarOperation, weekend: &[Weekday]) -> Calendar {
+        let mut calendar = Calendar::new_with_weekend(None, None, weekend);
+        calendar.composition = Some(Box::new(Composition { operation, left, right }));
+        calendar
     }
 }
 
+// a + b is a.combine(b).
 impl ops::Add<Calendar> for Calendar {
     type Output = Calendar;
     fn add(self, _rhs: Calendar) -> Self::Output {
@@ -251,26 +1200,36 @@ impl ops::Add<Calendar> for Calendar {
     }
 }
 
-pub fn get_ny_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_date: Option<i32>) -> Calendar {
-    let rules: Vec<Box<dyn HolidayRule>> = vec![
-        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
-        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::January)), // Martin Luther King Jr. Day
-        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::February)), // Presidents' Day
-        Box::new(FridayEasterRule), // Good Friday
-        Box::new(LastWeekWeekdayRule::new(Weekday::Mon, Month::May)), // Memorial Day
-        Box::new(MonthDayRule::new(Month::June, 19)), // Juneteenth
-        Box::new(MonthDayRule::new(Month::July, 4)), // Independence Day
-        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::September)), // Labor Day
-        Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::October)), // Columbus Day
-        Box::new(MonthDayRule::new(Month::November, 11)), // Veterans Day
-        Box::new(OrdinalWeekWeekdayRule::new(4, Weekday::Thu, Month::November)), // Thanksgiving Day
-        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
-    ];
-    let mut ny_c = Calendar::new(Some(rules), holidays);
-    match (start_year, end_date) {
+// a & b is a.intersect(b).
+impl ops::BitAnd<Calendar> for Calendar {
+    type Output = Calendar;
+    fn bitand(self, _rhs: Calendar) -> Self::Output {
+        self.intersect(_rhs)
+    }
+}
+
+// a - b is a.difference(b): holidays of a that are not holidays of b, so days closed in both are business days.
+impl ops::Sub<Calendar> for Calendar {
+    type Output = Calendar;
+    fn sub(self, _rhs: Calendar) -> Self::Output {
+        self.difference(_rhs)
+    }
+}
+
+// Calendar from holiday rules plus one-off special holidays and cancelled rule holidays (holidays moved in a given year
+// are cancelled on their usual date and added as special holidays on the new one). If start_year and end_year are set,
+// rule holidays in those years are added as explicit holidays.
+fn build_calendar(rules: Vec<Box<dyn HolidayRule>>, special_holidays: &[(i32, u32, u32)], cancelled_holidays: &[(i32, u32, u32)],
+                  holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let ymd = |&(y, m, d): &(i32, u32, u32)| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let mut calendar = Calendar::new(Some(rules), None);
+    calendar.remove_holidays_from_vec(cancelled_holidays.iter().map(ymd).collect());
+    calendar.add_holidays_from_vec(special_holidays.iter().map(ymd).collect());
+    calendar.add_holidays_from_vec(holidays.unwrap_or_default());
+    match (start_year, end_year) {
         (Some(sy), Some(ey))  => {
             if ey >= sy {
-                ny_c.add_holidays_with_rules(sy, ey)
+                calendar.add_holidays_with_rules(sy, ey)
             } else {
                 panic!("If start_year and end_year are set, start_year must be smaller or equal to end_year.");
             }
@@ -280,5 +1239,368 @@ pub fn get_ny_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>
         },
         (None, None) => ()
     }
-    ny_c
-}
\ No newline at end of file
+    calendar
+}
+
+pub fn get_ny_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_date: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 1)), Observance::SundayToMonday)), // New Year's Day (a Saturday New Year's Day is not observed on the 31st of December)
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::January)), // Martin Luther King Jr. Day
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::February)), // Presidents' Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(LastWeekWeekdayRule::new(Weekday::Mon, Month::May)), // Memorial Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::June, 19)), Observance::NearestWeekday)), // Juneteenth
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::July, 4)), Observance::NearestWeekday)), // Independence Day
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::September)), // Labor Day
+        Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::October)), // Columbus Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::November, 11)), Observance::NearestWeekday)), // Veterans Day
+        Box::new(OrdinalWeekWeekdayRule::new(4, Weekday::Thu, Month::November)), // Thanksgiving Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::December, 25)), Observance::NearestWeekday)), // Christmas Day
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_date)
+}
+
+// US bond market, following SIFMA recommendations. Good Friday was an early close instead of a full close in the years
+// the employment report was published on it.
+pub fn get_sifma_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 1)), Observance::SundayToMonday)), // New Year's Day
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::January)), // Martin Luther King Jr. Day
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::February)), // Presidents' Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(LastWeekWeekdayRule::new(Weekday::Mon, Month::May)), // Memorial Day
+        Box::new(YearRangeRule::new(Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::June, 19)), Observance::NearestWeekday)), Some(2022), None)), // Juneteenth
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::July, 4)), Observance::NearestWeekday)), // Independence Day
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::September)), // Labor Day
+        Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::October)), // Columbus Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::November, 11)), Observance::NearestWeekday)), // Veterans Day
+        Box::new(OrdinalWeekWeekdayRule::new(4, Weekday::Thu, Month::November)), // Thanksgiving Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::December, 25)), Observance::NearestWeekday)), // Christmas Day
+    ];
+    let special_holidays: [(i32, u32, u32); 2] = [
+        (2012, 10, 30), // Hurricane Sandy
+        (2018, 12, 5), // National Day of Mourning for George H. W. Bush
+    ];
+    let cancelled_holidays: [(i32, u32, u32); 4] = [(2012, 4, 6), (2015, 4, 3), (2021, 4, 2), (2023, 4, 7)]; // Good Friday early closes
+    build_calendar(rules, &special_holidays, &cancelled_holidays, holidays, start_year, end_year)
+}
+
+fn christmas_and_boxing_day_substitutes() -> Vec<Box<dyn HolidayRule>> {
+    let christmas = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 25)), vec![]);
+    let boxing_day = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 26)), vec![Box::new(christmas.clone())]);
+    vec![Box::new(christmas), Box::new(boxing_day)]
+}
+
+// England and Wales bank holidays.
+pub fn get_london_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let mut rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::January, 1)), vec![])), // New Year's Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(1)), // Easter Monday
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::May)), // Early May bank holiday
+        Box::new(LastWeekWeekdayRule::new(Weekday::Mon, Month::May)), // Spring bank holiday
+        Box::new(LastWeekWeekdayRule::new(Weekday::Mon, Month::August)), // Summer bank holiday
+    ];
+    rules.extend(christmas_and_boxing_day_substitutes());
+    let special_holidays: [(i32, u32, u32); 10] = [
+        (2002, 6, 3), (2002, 6, 4), // Golden Jubilee
+        (2011, 4, 29), // Royal wedding
+        (2012, 6, 4), (2012, 6, 5), // Diamond Jubilee
+        (2020, 5, 8), // VE day
+        (2022, 6, 2), (2022, 6, 3), // Platinum Jubilee
+        (2022, 9, 19), // State funeral of Queen Elizabeth II
+        (2023, 5, 8), // Coronation of King Charles III
+    ];
+    let cancelled_holidays: [(i32, u32, u32); 4] = [(2002, 5, 27), (2012, 5, 28), (2020, 5, 4), (2022, 5, 30)]; // Moved bank holidays
+    build_calendar(rules, &special_holidays, &cancelled_holidays, holidays, start_year, end_year)
+}
+
+// TARGET2 (euro area payments) closing days.
+pub fn get_target_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(1)), // Easter Monday
+        Box::new(MonthDayRule::new(Month::May, 1)), // Labour Day
+        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
+        Box::new(MonthDayRule::new(Month::December, 26)), // Christmas Holiday
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// Japanese national holidays plus bank holidays (2nd and 3rd of January, 31st of December). A national holiday falling
+// on a Sunday is substituted by the next day that is not a holiday.
+pub fn get_tokyo_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let sunday_substitute = |rule: Box<dyn HolidayRule>, taken: Vec<Box<dyn HolidayRule>>| -> Box<dyn HolidayRule> {
+        Box::new(SubstituteDayRule::new_with_weekend(rule, taken, &[Weekday::Sun]))
+    };
+    let respect_for_the_aged_day = YearRangeRule::new(Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::September)), Some(2003), None);
+    let autumnal_equinox = SeasonRule::new(Season::SeptemberEquinox, 9);
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(MonthDayRule::new(Month::January, 2)), // Bank holiday
+        Box::new(MonthDayRule::new(Month::January, 3)), // Bank holiday
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::January, 15)), vec![]), None, Some(1999))), // Coming of Age Day
+        Box::new(YearRangeRule::new(Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::January)), Some(2000), None)), // Coming of Age Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::February, 11)), vec![]), // National Foundation Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::February, 23)), vec![]), Some(2020), None)), // Emperor's Birthday
+        sunday_substitute(Box::new(SeasonRule::new(Season::MarchEquinox, 9)), vec![]), // Vernal Equinox Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::April, 29)), vec![]), // Showa Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::May, 3)), vec![Box::new(MonthDayRule::new(Month::May, 4)), Box::new(MonthDayRule::new(Month::May, 5))]), // Constitution Memorial Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::May, 4)), vec![Box::new(MonthDayRule::new(Month::May, 5))]), // Greenery Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::May, 5)), vec![]), // Children's Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::July, 20)), vec![]), Some(1996), Some(2002))), // Marine Day
+        Box::new(YearRangeRule::new(Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::July)), Some(2003), None)), // Marine Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::August, 11)), vec![]), Some(2016), None)), // Mountain Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::September, 15)), vec![]), None, Some(2002))), // Respect for the Aged Day
+        Box::new(respect_for_the_aged_day.clone()), // Respect for the Aged Day
+        Box::new(BridgeDayRule::new(Box::new(respect_for_the_aged_day), Box::new(autumnal_equinox.clone()))), // Citizens' Holiday
+        sunday_substitute(Box::new(autumnal_equinox), vec![]), // Autumnal Equinox Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::October, 10)), vec![]), None, Some(1999))), // Sports Day
+        Box::new(YearRangeRule::new(Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::October)), Some(2000), None)), // Sports Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::November, 3)), vec![]), // Culture Day
+        sunday_substitute(Box::new(MonthDayRule::new(Month::November, 23)), vec![]), // Labour Thanksgiving Day
+        Box::new(YearRangeRule::new(sunday_substitute(Box::new(MonthDayRule::new(Month::December, 23)), vec![]), Some(1989), Some(2018))), // Emperor's Birthday
+        Box::new(MonthDayRule::new(Month::December, 31)), // Bank holiday
+    ];
+    let special_holidays: [(i32, u32, u32); 10] = [
+        (2019, 4, 30), (2019, 5, 1), (2019, 5, 2), (2019, 10, 22), // Imperial succession
+        (2020, 7, 23), (2020, 7, 24), (2020, 8, 10), // Marine Day, Sports Day and Mountain Day moved for the Olympic Games
+        (2021, 7, 22), (2021, 7, 23), (2021, 8, 9), // Same in 2021 (Mountain Day on Sunday the 8th)
+    ];
+    let cancelled_holidays: [(i32, u32, u32); 6] = [(2020, 7, 20), (2020, 8, 11), (2020, 10, 12), (2021, 7, 19), (2021, 8, 11), (2021, 10, 11)];
+    build_calendar(rules, &special_holidays, &cancelled_holidays, holidays, start_year, end_year)
+}
+
+// Swiss banking holidays.
+pub fn get_zurich_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(MonthDayRule::new(Month::January, 2)), // Berchtoldstag
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(1)), // Easter Monday
+        Box::new(EasterOffsetRule::new(39)), // Ascension Day
+        Box::new(EasterOffsetRule::new(50)), // Whit Monday
+        Box::new(MonthDayRule::new(Month::May, 1)), // Labour Day
+        Box::new(MonthDayRule::new(Month::August, 1)), // National Day
+        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
+        Box::new(MonthDayRule::new(Month::December, 26)), // St. Stephen's Day
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// Canadian settlement holidays (Ontario). Holidays falling on a weekend are observed on the next Monday.
+pub fn get_toronto_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let mut rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 1)), Observance::NextMonday)), // New Year's Day
+        Box::new(YearRangeRule::new(Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::February)), Some(2008), None)), // Family Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::May, 24)), [0, -1, -2, -3, -4, -5, -6])), // Victoria Day (Monday on or before the 24th of May)
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::July, 1)), Observance::NextMonday)), // Canada Day
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::August)), // Civic Holiday
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::September)), // Labour Day
+        Box::new(YearRangeRule::new(Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::September, 30)), Observance::NextMonday)), Some(2021), None)), // National Day for Truth and Reconciliation
+        Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::October)), // Thanksgiving Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::November, 11)), Observance::NextMonday)), // Remembrance Day
+    ];
+    rules.extend(christmas_and_boxing_day_substitutes());
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// New South Wales bank holidays.
+pub fn get_sydney_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let mut rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 1)), Observance::NextMonday)), // New Year's Day
+        Box::new(ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 26)), Observance::NextMonday)), // Australia Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(1)), // Easter Monday
+        Box::new(MonthDayRule::new(Month::April, 25)), // Anzac Day (not moved when on a weekend)
+        Box::new(OrdinalWeekWeekdayRule::new(2, Weekday::Mon, Month::June)), // King's Birthday
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::August)), // Bank Holiday
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::October)), // Labour Day
+    ];
+    rules.extend(christmas_and_boxing_day_substitutes());
+    let special_holidays: [(i32, u32, u32); 1] = [(2022, 9, 22)]; // National Day of Mourning for Queen Elizabeth II
+    build_calendar(rules, &special_holidays, &[], holidays, start_year, end_year)
+}
+
+// B3 exchange holidays. B3 stays open on São Paulo city holidays since 2022 and closes on the last weekday of the year.
+pub fn get_sao_paulo_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(YearRangeRule::new(Box::new(MonthDayRule::new(Month::January, 25)), None, Some(2021))), // São Paulo Anniversary
+        Box::new(EasterOffsetRule::new(-48)), // Carnival Monday
+        Box::new(EasterOffsetRule::new(-47)), // Carnival Tuesday
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(MonthDayRule::new(Month::April, 21)), // Tiradentes
+        Box::new(MonthDayRule::new(Month::May, 1)), // Labour Day
+        Box::new(EasterOffsetRule::new(60)), // Corpus Christi
+        Box::new(YearRangeRule::new(Box::new(MonthDayRule::new(Month::July, 9)), None, Some(2021))), // Constitutionalist Revolution
+        Box::new(MonthDayRule::new(Month::September, 7)), // Independence Day
+        Box::new(MonthDayRule::new(Month::October, 12)), // Our Lady of Aparecida
+        Box::new(MonthDayRule::new(Month::November, 2)), // All Souls' Day
+        Box::new(MonthDayRule::new(Month::November, 15)), // Republic Proclamation Day
+        Box::new(YearRangeRule::new(Box::new(MonthDayRule::new(Month::November, 20)), None, Some(2021))), // Black Consciousness Day (São Paulo)
+        Box::new(YearRangeRule::new(Box::new(MonthDayRule::new(Month::November, 20)), Some(2024), None)), // Black Consciousness Day (national)
+        Box::new(MonthDayRule::new(Month::December, 24)), // Christmas Eve
+        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::December, 31)), [0, 0, 0, 0, 0, -1, -2])), // Last weekday of the year
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// Chilean bank holidays. Some holidays are moved to Mondays (or Fridays) depending on the weekday they fall on.
+pub fn get_santiago_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let to_monday: [i64; 7] = [0, -1, -2, -3, 3, 0, 0];
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(YearRangeRule::new(Box::new(OnWeekdaysRule::new(Box::new(MonthDayRule::new(Month::January, 2)), &[Weekday::Mon])), Some(2017), None)), // Holiday after a Sunday New Year's Day
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(-1)), // Holy Saturday
+        Box::new(MonthDayRule::new(Month::May, 1)), // Labour Day
+        Box::new(MonthDayRule::new(Month::May, 21)), // Navy Day
+        Box::new(YearRangeRule::new(Box::new(SeasonRule::new(Season::JuneSolstice, -4)), Some(2021), None)), // National Day of Indigenous Peoples
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::June, 29)), to_monday)), // Saint Peter and Saint Paul
+        Box::new(MonthDayRule::new(Month::July, 16)), // Our Lady of Mount Carmel
+        Box::new(MonthDayRule::new(Month::August, 15)), // Assumption of Mary
+        Box::new(OnWeekdaysRule::new(Box::new(MonthDayRule::new(Month::September, 17)), &[Weekday::Mon])), // Bridge before Independence Day
+        Box::new(MonthDayRule::new(Month::September, 18)), // Independence Day
+        Box::new(MonthDayRule::new(Month::September, 19)), // Army Day
+        Box::new(OnWeekdaysRule::new(Box::new(MonthDayRule::new(Month::September, 20)), &[Weekday::Fri])), // Bridge after Army Day
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::October, 12)), to_monday)), // Meeting of Two Worlds
+        Box::new(YearRangeRule::new(Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::October, 31)), [0, -4, 2, 0, 0, 0, 0])), Some(2008), None)), // Reformation Day
+        Box::new(MonthDayRule::new(Month::November, 1)), // All Saints' Day
+        Box::new(MonthDayRule::new(Month::December, 8)), // Immaculate Conception
+        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
+        Box::new(MonthDayRule::new(Month::December, 31)), // Bank holiday
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// Mexican bank holidays (CNBV).
+pub fn get_mexico_city_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(MonthDayRule::new(Month::January, 1)), // New Year's Day
+        Box::new(OrdinalWeekWeekdayRule::new(1, Weekday::Mon, Month::February)), // Constitution Day
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::March)), // Benito Juárez's Birthday
+        Box::new(EasterOffsetRule::new(-3)), // Holy Thursday
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(MonthDayRule::new(Month::May, 1)), // Labour Day
+        Box::new(MonthDayRule::new(Month::September, 16)), // Independence Day
+        Box::new(MonthDayRule::new(Month::November, 2)), // All Souls' Day
+        Box::new(OrdinalWeekWeekdayRule::new(3, Weekday::Mon, Month::November)), // Revolution Day
+        Box::new(MonthDayRule::new(Month::December, 12)), // Our Lady of Guadalupe
+        Box::new(MonthDayRule::new(Month::December, 25)), // Christmas Day
+    ];
+    let special_holidays: [(i32, u32, u32); 2] = [(2024, 10, 1), (2030, 10, 1)]; // Presidential inaugurations
+    build_calendar(rules, &special_holidays, &[], holidays, start_year, end_year)
+}
+
+// Hong Kong general holidays (HKEX). A holiday falling on a Sunday moves to the next day that is not a holiday, Saturday
+// holidays are not moved.
+pub fn get_hong_kong_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let sunday_substitute = |rule: Box<dyn HolidayRule>, taken: Vec<Box<dyn HolidayRule>>| -> Box<dyn HolidayRule> {
+        Box::new(SubstituteDayRule::new_with_weekend(rule, taken, &[Weekday::Sun]))
+    };
+    let easter_monday = EasterOffsetRule::new(1);
+    let day_after_mid_autumn = LunarDateRule::new(LunarCalendar::Chinese, 8, 16);
+    let national_day = MonthDayRule::new(Month::October, 1);
+    let first_weekday_after_christmas = MonthDayRule::new(Month::December, 26);
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        sunday_substitute(Box::new(MonthDayRule::new(Month::January, 1)), vec![]), // New Year's Day
+        sunday_substitute(Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), 0, 3)), vec![]), // Lunar New Year
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        Box::new(EasterOffsetRule::new(-1)), // Day following Good Friday
+        Box::new(easter_monday.clone()), // Easter Monday
+        sunday_substitute(Box::new(SolarTermRule::new(15, 8)), vec![Box::new(easter_monday)]), // Ching Ming Festival
+        sunday_substitute(Box::new(MonthDayRule::new(Month::May, 1)), vec![]), // Labour Day
+        sunday_substitute(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 4, 8)), vec![]), // Buddha's Birthday
+        sunday_substitute(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 5, 5)), vec![]), // Tuen Ng Festival
+        sunday_substitute(Box::new(MonthDayRule::new(Month::July, 1)), vec![]), // HKSAR Establishment Day
+        sunday_substitute(Box::new(day_after_mid_autumn.clone()), vec![Box::new(national_day.clone())]), // Day following Mid-Autumn Festival
+        sunday_substitute(Box::new(national_day), vec![Box::new(day_after_mid_autumn)]), // National Day
+        sunday_substitute(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 9, 9)), vec![]), // Chung Yeung Festival
+        sunday_substitute(Box::new(MonthDayRule::new(Month::December, 25)), vec![Box::new(first_weekday_after_christmas.clone())]), // Christmas Day
+        sunday_substitute(Box::new(first_weekday_after_christmas), vec![]), // First weekday after Christmas Day
+    ];
+    build_calendar(rules, &[], &[], holidays, start_year, end_year)
+}
+
+// Singapore public holidays (SGX). Vesak Day, Hari Raya Puasa, Hari Raya Haji and Deepavali are announced every year by the
+// Ministry of Manpower and are tabulated up to 2026, later dates have to be passed in holidays.
+pub fn get_singapore_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let sunday_substitute = |rule: Box<dyn HolidayRule>| -> Box<dyn HolidayRule> {
+        Box::new(SubstituteDayRule::new_with_weekend(rule, vec![], &[Weekday::Sun]))
+    };
+    let tabulated = |dates: &[(i32, u32, u32)]| -> Box<dyn HolidayRule> {
+        Box::new(TabulatedRule::new(dates.iter().map(|&(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap()).collect()))
+    };
+    let hari_raya_puasa: [(i32, u32, u32); 8] = [
+        (2019, 6, 5), (2020, 5, 24), (2021, 5, 13), (2022, 5, 3), (2023, 4, 22), (2024, 4, 10), (2025, 3, 31), (2026, 3, 21),
+    ];
+    let vesak_day: [(i32, u32, u32); 8] = [
+        (2019, 5, 19), (2020, 5, 7), (2021, 5, 26), (2022, 5, 15), (2023, 6, 2), (2024, 5, 22), (2025, 5, 12), (2026, 5, 31),
+    ];
+    let hari_raya_haji: [(i32, u32, u32); 8] = [
+        (2019, 8, 11), (2020, 7, 31), (2021, 7, 20), (2022, 7, 10), (2023, 6, 29), (2024, 6, 17), (2025, 6, 7), (2026, 5, 27),
+    ];
+    let deepavali: [(i32, u32, u32); 8] = [
+        (2019, 10, 27), (2020, 11, 14), (2021, 11, 4), (2022, 10, 24), (2023, 11, 12), (2024, 10, 31), (2025, 10, 20), (2026, 11, 8),
+    ];
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        sunday_substitute(Box::new(MonthDayRule::new(Month::January, 1))), // New Year's Day
+        sunday_substitute(Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), 0, 2))), // Chinese New Year
+        Box::new(EasterOffsetRule::new(-2)), // Good Friday
+        sunday_substitute(tabulated(&hari_raya_puasa)), // Hari Raya Puasa
+        sunday_substitute(Box::new(MonthDayRule::new(Month::May, 1))), // Labour Day
+        sunday_substitute(tabulated(&vesak_day)), // Vesak Day
+        sunday_substitute(tabulated(&hari_raya_haji)), // Hari Raya Haji
+        sunday_substitute(Box::new(MonthDayRule::new(Month::August, 9))), // National Day
+        sunday_substitute(tabulated(&deepavali)), // Deepavali
+        sunday_substitute(Box::new(MonthDayRule::new(Month::December, 25))), // Christmas Day
+    ];
+    let special_holidays: [(i32, u32, u32); 3] = [(2020, 7, 10), (2023, 9, 1), (2025, 5, 3)]; // Polling days
+    build_calendar(rules, &special_holidays, &[], holidays, start_year, end_year)
+}
+
+// Shanghai Stock Exchange closing days. Holidays falling on a Tuesday or Thursday are bridged to the weekend (the State Council
+// makes up for the bridge day with a working weekend day) and the exact Spring Festival, Labour Day and National Day breaks
+// are announced every year; the rules give the usual breaks and past announcements are special or cancelled holidays.
+pub fn get_shanghai_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let to_monday: [i64; 7] = [0, 0, 0, 0, 0, 2, 1];
+    let bridged = |rule: Box<dyn HolidayRule>| -> Vec<Box<dyn HolidayRule>> {
+        let bridge_day = WeekdayShiftRule::new(Box::new(OnWeekdaysRule::new(rule.clone(), &[Weekday::Tue, Weekday::Thu])), [0, -1, 0, 1, 0, 0, 0]);
+        vec![Box::new(WeekdayShiftRule::new(rule, to_monday)), Box::new(bridge_day)]
+    };
+    let mut rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::January, 1)), to_monday)), // New Year's Day
+        Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), -1, 7)), // Spring Festival, from the eve to the sixth day
+        Box::new(DaySpanRule::new(Box::new(MonthDayRule::new(Month::May, 1)), 0, 5)), // Labour Day
+        Box::new(DaySpanRule::new(Box::new(MonthDayRule::new(Month::October, 1)), 0, 7)), // National Day
+    ];
+    rules.extend(bridged(Box::new(SolarTermRule::new(15, 8)))); // Qingming Festival
+    rules.extend(bridged(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 5, 5)))); // Dragon Boat Festival
+    rules.extend(bridged(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 8, 15)))); // Mid-Autumn Festival
+    let special_holidays: [(i32, u32, u32); 5] = [
+        (2020, 1, 31), // Spring Festival extended for the Covid-19 outbreak
+        (2020, 10, 8), (2025, 10, 8), // Mid-Autumn Festival joined to the National Day break
+        (2024, 2, 16), (2025, 2, 4), // Eighth day of the Spring Festival break
+    ];
+    let cancelled_holidays: [(i32, u32, u32); 3] = [(2022, 5, 5), (2023, 5, 4), (2023, 5, 5)]; // Labour Day break starting on Saturday
+    build_calendar(rules, &special_holidays, &cancelled_holidays, holidays, start_year, end_year)
+}
+
+// Saudi Exchange (Tadawul) holidays, with a Friday and Saturday weekend. Eid breaks are the ones of the Labour Law, computed
+// with the Umm al-Qura calendar; longer breaks announced by the exchange have to be passed in holidays.
+pub fn get_riyadh_calendar(holidays: Option<Vec<NaiveDate>>, start_year: Option<i32>, end_year: Option<i32>) -> Calendar {
+    let weekend_shift: [i64; 7] = [0, 0, 0, 0, -1, 1, 0]; // Friday => Thursday, Saturday => Sunday
+    let rules: Vec<Box<dyn HolidayRule>> = vec![
+        Box::new(YearRangeRule::new(Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::February, 22)), weekend_shift)), Some(2022), None)), // Founding Day
+        Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Hijri, 9, 29)), 1, 4)), // Eid al-Fitr, four days from the day after 29 Ramadan
+        Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Hijri, 12, 9)), 0, 4)), // Arafat Day and Eid al-Adha
+        Box::new(WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::September, 23)), weekend_shift)), // National Day
+    ];
+    let mut calendar = build_calendar(rules, &[], &[], holidays, start_year, end_year);
+    calendar.set_weekend(&FRIDAY_SATURDAY);
+    calendar
+}
@@ -1,10 +1,19 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
 
 use crate::dates::calendars::Calendar;
+use crate::dates::aux_funcs::get_eom;
 
 #[allow(dead_code)]
 pub trait DateAdjustingMethod {
     fn adjust(&self, date: NaiveDate) -> NaiveDate;
+
+    // Adjusts the dates of a schedule, in order. Conventions that depend on earlier dates (FloatingRateNote) override it.
+    fn adjust_schedule(&self, dates: &[NaiveDate]) -> Vec<NaiveDate> {
+        dates.iter().map(|date| self.adjust(*date)).collect()
+    }
 }
 
 pub struct Following<'a> {
@@ -17,6 +26,9 @@ impl<'a> Following<'a> {
 }
 impl<'a> DateAdjustingMethod for Following<'a> {
     fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        if self.calendar.is_business_day(date) {
+            return date;
+        }
         self.calendar.add_business_days(date, 1)
     }
 }
@@ -52,6 +64,9 @@ impl<'a> Preceding<'a> {
 }
 impl<'a> DateAdjustingMethod for Preceding<'a> {
     fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        if self.calendar.is_business_day(date) {
+            return date;
+        }
         self.calendar.substract_business_days(date, 1)
     }
 }
@@ -77,4 +92,179 @@ impl<'a> DateAdjustingMethod for ModifiedPreceding<'a> {
         }
         adjusted_date
     }
-}
\ No newline at end of file
+}
+
+pub struct Unadjusted;
+impl DateAdjustingMethod for Unadjusted {
+    fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        date
+    }
+}
+
+// Nearest business day. If following and preceding business days are equally far, following is used.
+pub struct Nearest<'a> {
+    f_adj: Following<'a>,
+    p_adj: Preceding<'a>,
+}
+impl<'a> Nearest<'a> {
+    pub fn new(calendar: &'a Calendar) -> Self {
+        let f_adj = Following::new(calendar);
+        let p_adj = Preceding::new(calendar);
+        Nearest { f_adj, p_adj }
+    }
+}
+impl<'a> DateAdjustingMethod for Nearest<'a> {
+    fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        let following = self.f_adj.adjust(date);
+        let preceding = self.p_adj.adjust(date);
+        if following - date <= date - preceding {
+            return following;
+        }
+        preceding
+    }
+}
+
+// Modified following that also goes back when following crosses the 15th of the month.
+pub struct HalfMonthModifiedFollowing<'a> {
+    f_adj: Following<'a>,
+    p_adj: Preceding<'a>,
+}
+impl<'a> HalfMonthModifiedFollowing<'a> {
+    pub fn new(calendar: &'a Calendar) -> Self {
+        let f_adj = Following::new(calendar);
+        let p_adj = Preceding::new(calendar);
+        HalfMonthModifiedFollowing { f_adj, p_adj }
+    }
+}
+impl<'a> DateAdjustingMethod for HalfMonthModifiedFollowing<'a> {
+    fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        let adjusted_date = self.f_adj.adjust(date);
+        if adjusted_date.month() != date.month() || (date.day() <= 15 && adjusted_date.day() > 15) {
+            return self.p_adj.adjust(date);
+        }
+        adjusted_date
+    }
+}
+
+// FRN (Eurodollar) convention: modified following, but once a date of a schedule lands on the last business day
+// of its month, every later date is the last business day of its month.
+// adjust works date by date, so it only knows about end of month alignment if built with new_end_of_month; use
+// adjust_schedule to adjust the dates of a schedule.
+pub struct FloatingRateNote<'a> {
+    calendar: &'a Calendar,
+    mf_adj: ModifiedFollowing<'a>,
+    end_of_month: bool,
+}
+impl<'a> FloatingRateNote<'a> {
+    pub fn new(calendar: &'a Calendar) -> Self {
+        FloatingRateNote { calendar, mf_adj: ModifiedFollowing::new(calendar), end_of_month: false }
+    }
+
+    pub fn new_end_of_month(calendar: &'a Calendar) -> Self {
+        FloatingRateNote { calendar, mf_adj: ModifiedFollowing::new(calendar), end_of_month: true }
+    }
+
+    fn last_business_day_of_month(&self, date: NaiveDate) -> NaiveDate {
+        let eom: NaiveDate = get_eom(date);
+        if self.calendar.is_business_day(eom) {
+            return eom;
+        }
+        self.calendar.substract_business_days(eom, 1)
+    }
+}
+impl<'a> DateAdjustingMethod for FloatingRateNote<'a> {
+    fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        if self.end_of_month {
+            return self.last_business_day_of_month(date);
+        }
+        self.mf_adj.adjust(date)
+    }
+
+    fn adjust_schedule(&self, dates: &[NaiveDate]) -> Vec<NaiveDate> {
+        let mut end_of_month: bool = self.end_of_month;
+        let mut adjusted_dates: Vec<NaiveDate> = Vec::with_capacity(dates.len());
+        for date in dates {
+            let adjusted_date: NaiveDate = if end_of_month {
+                self.last_business_day_of_month(*date)
+            } else {
+                self.mf_adj.adjust(*date)
+            };
+            end_of_month = end_of_month || adjusted_date == self.last_business_day_of_month(adjusted_date);
+            adjusted_dates.push(adjusted_date);
+        }
+        adjusted_dates
+    }
+}
+
+// Calendar independent business day convention, to be stored with trade data and bound to a calendar when needed.
+// Parsing is case insensitive and ignores spaces, '-' and '_' ("MF", "Modified Following", "MODFOLLOWING" are the same).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BusinessDayConvention {
+    Unadjusted,
+    Following,
+    ModifiedFollowing,
+    Preceding,
+    ModifiedPreceding,
+    Nearest,
+    HalfMonthModifiedFollowing,
+    FloatingRateNote,
+}
+impl BusinessDayConvention {
+    pub fn bind<'a>(&self, calendar: &'a Calendar) -> Box<dyn DateAdjustingMethod + 'a> {
+        match self {
+            BusinessDayConvention::Unadjusted => Box::new(Unadjusted),
+            BusinessDayConvention::Following => Box::new(Following::new(calendar)),
+            BusinessDayConvention::ModifiedFollowing => Box::new(ModifiedFollowing::new(calendar)),
+            BusinessDayConvention::Preceding => Box::new(Preceding::new(calendar)),
+            BusinessDayConvention::ModifiedPreceding => Box::new(ModifiedPreceding::new(calendar)),
+            BusinessDayConvention::Nearest => Box::new(Nearest::new(calendar)),
+            BusinessDayConvention::HalfMonthModifiedFollowing => Box::new(HalfMonthModifiedFollowing::new(calendar)),
+            BusinessDayConvention::FloatingRateNote => Box::new(FloatingRateNote::new(calendar)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBusinessDayConventionError {
+    pub input: String,
+}
+impl fmt::Display for ParseBusinessDayConventionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown business day convention '{}'.", self.input)
+    }
+}
+impl Error for ParseBusinessDayConventionError {}
+
+impl FromStr for BusinessDayConvention {
+    type Err = ParseBusinessDayConventionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key: String = s.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).collect::<String>().to_uppercase();
+        match &key[..] {
+            "U" | "NONE" | "UNADJUSTED" | "NOADJUSTMENT" | "ACTUAL" => Ok(BusinessDayConvention::Unadjusted),
+            "F" | "FOL" | "FOLLOWING" => Ok(BusinessDayConvention::Following),
+            "MF" | "MODFOL" | "MODFOLLOWING" | "MODIFIEDFOLLOWING" => Ok(BusinessDayConvention::ModifiedFollowing),
+            "P" | "PRE" | "PRECEDING" => Ok(BusinessDayConvention::Preceding),
+            "MP" | "MODPRE" | "MODPRECEDING" | "MODIFIEDPRECEDING" => Ok(BusinessDayConvention::ModifiedPreceding),
+            "N" | "NEAREST" => Ok(BusinessDayConvention::Nearest),
+            "HMMF" | "HALFMONTHMF" | "HALFMONTHMODIFIEDFOLLOWING" | "MODIFIEDFOLLOWINGHALFMONTH" => Ok(BusinessDayConvention::HalfMonthModifiedFollowing),
+            "FRN" | "EURODOLLAR" | "FLOATINGRATENOTE" => Ok(BusinessDayConvention::FloatingRateNote),
+            _ => Err(ParseBusinessDayConventionError { input: s.to_string() }),
+        }
+    }
+}
+
+impl fmt::Display for BusinessDayConvention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: &str = match self {
+            BusinessDayConvention::Unadjusted => "Unadjusted",
+            BusinessDayConvention::Following => "Following",
+            BusinessDayConvention::ModifiedFollowing => "ModifiedFollowing",
+            BusinessDayConvention::Preceding => "Preceding",
+            BusinessDayConvention::ModifiedPreceding => "ModifiedPreceding",
+            BusinessDayConvention::Nearest => "Nearest",
+            BusinessDayConvention::HalfMonthModifiedFollowing => "HalfMonthModifiedFollowing",
+            BusinessDayConvention::FloatingRateNote => "FRN",
+        };
+        write!(f, "{}", name)
+    }
+}
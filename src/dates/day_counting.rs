@@ -1,12 +1,13 @@
 use chrono::{Datelike, NaiveDate};
 use std::cmp::min;
-use crate::dates::aux_funcs::{get_current_year_end_of_february, get_days_in_month};
+use crate::dates::aux_funcs::{count_leap_days, get_current_year_end_of_february, get_days_in_month};
+use crate::dates::calendars::Calendar;
 
 
 pub trait DayCounter {
     fn day_count(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32;
 
-    fn day_count_vector(&self, start_date: NaiveDate, end_dates: &Vec<NaiveDate>) -> Vec<i32> {
+    fn day_count_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<i32> {
         end_dates.iter().map(|end_date| self.day_count(start_date, *end_date)).collect()
     }
 }
@@ -14,15 +15,43 @@ pub trait DayCounter {
 pub struct ActualCounter;
 impl DayCounter for ActualCounter {
     fn day_count(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32 {
-        return end_date.num_days_from_ce() - start_date.num_days_from_ce();
+        end_date.num_days_from_ce() - start_date.num_days_from_ce()
     }
     
-    fn day_count_vector(&self, start_date: NaiveDate, end_dates: &Vec<NaiveDate>) -> Vec<i32> {
+    fn day_count_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<i32> {
         let sdi = start_date.num_days_from_ce();
         end_dates.iter().map(|end_date| end_date.num_days_from_ce() - sdi).collect()
     }
 }
 
+// Actual days excluding 29th of February. Used for NL/365.
+pub struct NoLeapCounter;
+impl DayCounter for NoLeapCounter {
+    fn day_count(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32 {
+        end_date.num_days_from_ce() - start_date.num_days_from_ce() - count_leap_days(start_date, end_date)
+    }
+
+    fn day_count_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<i32> {
+        let sdi = start_date.num_days_from_ce();
+        end_dates.iter().map(|end_date| end_date.num_days_from_ce() - sdi - count_leap_days(start_date, *end_date)).collect()
+    }
+}
+
+// Counts business days in [start_date, end_date) on a calendar. Used for BUS/252.
+pub struct BusinessDayCounter<'a> {
+    calendar: &'a Calendar,
+}
+impl<'a> BusinessDayCounter<'a> {
+    pub fn new(calendar: &'a Calendar) -> Self {
+        BusinessDayCounter { calendar }
+    }
+}
+impl<'a> DayCounter for BusinessDayCounter<'a> {
+    fn day_count(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32 {
+        self.calendar.business_days_between(start_date, end_date) as i32
+    }
+}
+
 pub trait Days30Backend {
     fn get_d1(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32;
     fn get_d2(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32;
@@ -40,6 +69,7 @@ impl DayCounter for Days30Counter {
     }
 }
 
+// 30/360 Bond Basis (ISDA 2006 4.16(f)): D1 = min(d1, 30), D2 = min(d2, 30) only if D1 is 30.
 pub struct Days30BondCounter;
 impl Days30Backend for Days30BondCounter {
     fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
@@ -56,22 +86,49 @@ impl Days30Backend for Days30BondCounter {
     }
 }
 
-pub struct Days30ECounter ;
+// 30E/360 Eurobond Basis (ISDA 2006 4.16(g)): D1 = min(d1, 30), D2 = min(d2, 30).
+pub struct Days30ECounter;
 impl Days30Backend for Days30ECounter {
     fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
         let d1: i32 = start_date.day() as i32;
         min(d1, 30)
     }
-    fn get_d2(&self, start_date: NaiveDate, end_date: NaiveDate) -> i32 {
-        let d1 = self.get_d1(start_date, end_date);
-        let mut d2: i32 = end_date.day() as i32;
-        if d1 > 29 {
-            d2 = min(d2, 30);
+    fn get_d2(&self, _: NaiveDate, end_date: NaiveDate) -> i32 {
+        let d2: i32 = end_date.day() as i32;
+        min(d2, 30)
+    }
+}
+
+// 30E+/360: D1 = min(d1, 30). If d2 is 31, end date moves to the 1st of the next month, which counts the same as keeping D2 = 31.
+pub struct Days30EPlusCounter;
+impl Days30Backend for Days30EPlusCounter {
+    fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
+        let d1: i32 = start_date.day() as i32;
+        min(d1, 30)
+    }
+    fn get_d2(&self, _: NaiveDate, end_date: NaiveDate) -> i32 {
+        end_date.day() as i32
+    }
+}
+
+// 30/360 German: D1 = 30 if d1 is 31 or the last day of February, same for D2. No exception at maturity.
+pub struct Days30GermanCounter;
+impl Days30Backend for Days30GermanCounter {
+    fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
+        if start_date.day() == 31 || start_date == get_current_year_end_of_february(start_date) {
+            return 30;
         }
-        d2
+        start_date.day() as i32
+    }
+    fn get_d2(&self, _: NaiveDate, end_date: NaiveDate) -> i32 {
+        if end_date.day() == 31 || end_date == get_current_year_end_of_february(end_date) {
+            return 30;
+        }
+        end_date.day() as i32
     }
 }
 
+// 30/360 US (SIA): D1 = 30 if d1 is the last day of February or 31, D2 = 30 if both dates are the last day of February or if d2 is 31 and D1 is 30.
 pub struct Days30UCounter;
 impl Days30Backend for Days30UCounter {
     fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
@@ -103,7 +160,16 @@ impl Days30Backend for Days30UCounter {
     }
 }
 
-pub struct Days30EISDACounter;
+// 30E/360 ISDA (ISDA 2006 4.16(h)): D1 = 30 if d1 is the last day of the month, same for D2 unless end_date
+// is the termination date and is in February.
+pub struct Days30EISDACounter {
+    pub termination_date: Option<NaiveDate>,
+}
+impl Days30EISDACounter {
+    pub fn new(termination_date: Option<NaiveDate>) -> Self {
+        Days30EISDACounter { termination_date }
+    }
+}
 impl Days30Backend for Days30EISDACounter {
     fn get_d1(&self, start_date: NaiveDate, _: NaiveDate) -> i32 {
         let s_eom: u32 = get_days_in_month(start_date);
@@ -118,8 +184,10 @@ impl Days30Backend for Days30EISDACounter {
     fn get_d2(&self, _: NaiveDate, end_date: NaiveDate) -> i32 {
         let e_eom: u32 = get_days_in_month(end_date);
 
+        let is_february_termination: bool = end_date.month() == 2 && Some(end_date) == self.termination_date;
+
         let mut d2: u32 = end_date.day();
-        if d2 == e_eom {
+        if d2 == e_eom && !is_february_termination {
             d2 = 30;
         }
         d2 as i32
@@ -0,0 +1,153 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::error::Error;
+use std::fmt;
+
+use crate::dates::calendars::{HolidayRule, OrdinalWeekWeekdayRule};
+use crate::dates::tenors::{Tenor, TenorDateError};
+
+// IMM dates are the third Wednesday of a month. The main cycle are March, June, September and December (futures
+// contracts H, M, U and Z).
+const MONTH_CODES: [char; 12] = ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+fn is_main_cycle_month(month: u32) -> bool {
+    month.is_multiple_of(3)
+}
+
+fn add_months(year: i32, month: u32, months: i32) -> (i32, u32) {
+    let total: i32 = year * 12 + month as i32 - 1 + months;
+    (total.div_euclid(12), total.rem_euclid(12) as u32 + 1)
+}
+
+pub fn imm_date(year: i32, month: u32) -> NaiveDate {
+    OrdinalWeekWeekdayRule { month, ordinal: 3, weekday: Weekday::Wed }
+        .get_date(year)
+        .unwrap_or_else(|| panic!("No IMM date in {}-{:02}.", year, month))
+}
+
+pub fn is_imm_date(date: NaiveDate, main_cycle: bool) -> bool {
+    (!main_cycle || is_main_cycle_month(date.month())) && date == imm_date(date.year(), date.month())
+}
+
+// First IMM date strictly after date.
+pub fn next_imm_date(date: NaiveDate, main_cycle: bool) -> NaiveDate {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        if !main_cycle || is_main_cycle_month(month) {
+            let candidate: NaiveDate = imm_date(year, month);
+            if candidate > date {
+                return candidate;
+            }
+        }
+        (year, month) = add_months(year, month, 1);
+    }
+}
+
+// Last IMM date strictly before date.
+pub fn previous_imm_date(date: NaiveDate, main_cycle: bool) -> NaiveDate {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        if !main_cycle || is_main_cycle_month(month) {
+            let candidate: NaiveDate = imm_date(year, month);
+            if candidate < date {
+                return candidate;
+            }
+        }
+        (year, month) = add_months(year, month, -1);
+    }
+}
+
+// Contract code of an IMM date, with a one digit year ("H5" for 2025-03-19). None if date is not an IMM date.
+pub fn imm_code(date: NaiveDate) -> Option<String> {
+    if !is_imm_date(date, false) {
+        return None;
+    }
+    Some(format!("{}{}", MONTH_CODES[date.month0() as usize], date.year().rem_euclid(10)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseImmCodeError {
+    pub input: String,
+}
+impl fmt::Display for ParseImmCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid IMM code '{}', expected a month letter (F, G, H, J, K, M, N, Q, U, V, X, Z) and a one or two digit year.", self.input)
+    }
+}
+impl Error for ParseImmCodeError {}
+
+// IMM date of a contract code ("H5", "Z24"), taken as the first one on or after reference_date.
+pub fn imm_date_from_code(code: &str, reference_date: NaiveDate) -> Result<NaiveDate, ParseImmCodeError> {
+    let error = || ParseImmCodeError { input: code.to_string() };
+    let normalized: String = code.trim().to_uppercase();
+    let mut chars = normalized.chars();
+    let month_code: char = chars.next().ok_or_else(error)?;
+    let month: u32 = MONTH_CODES.iter().position(|c| *c == month_code).ok_or_else(error)? as u32 + 1;
+    let digits: &str = chars.as_str();
+    if digits.is_empty() || digits.len() > 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(error());
+    }
+    let year_in_cycle: i32 = digits.parse().map_err(|_| error())?;
+    let cycle: i32 = 10_i32.pow(digits.len() as u32);
+
+    let mut year: i32 = reference_date.year() - reference_date.year().rem_euclid(cycle) + year_in_cycle;
+    if imm_date(year, month) < reference_date {
+        year += cycle;
+    }
+    Ok(imm_date(year, month))
+}
+
+// CDS dates are the 20th of March, June, September and December. Coupons are paid on every CDS date, while standard
+// maturities roll semiannually (since December 2015): trades from 20 March to 19 September mature on a 20 June, trades
+// from 20 September to 19 March on a 20 December.
+pub fn is_cds_date(date: NaiveDate) -> bool {
+    is_main_cycle_month(date.month()) && date.day() == 20
+}
+
+// First CDS date strictly after date.
+pub fn next_cds_date(date: NaiveDate) -> NaiveDate {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        if is_main_cycle_month(month) {
+            let candidate: NaiveDate = NaiveDate::from_ymd_opt(year, month, 20).unwrap();
+            if candidate > date {
+                return candidate;
+            }
+        }
+        (year, month) = add_months(year, month, 1);
+    }
+}
+
+// Last CDS date strictly before date.
+pub fn previous_cds_date(date: NaiveDate) -> NaiveDate {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        if is_main_cycle_month(month) {
+            let candidate: NaiveDate = NaiveDate::from_ymd_opt(year, month, 20).unwrap();
+            if candidate < date {
+                return candidate;
+            }
+        }
+        (year, month) = add_months(year, month, -1);
+    }
+}
+
+// 20 June or 20 December the standard maturities of a trade on trade_date are rolled from.
+pub fn cds_roll_date(trade_date: NaiveDate) -> NaiveDate {
+    let year: i32 = trade_date.year();
+    let march_roll: NaiveDate = NaiveDate::from_ymd_opt(year, 3, 20).unwrap();
+    let september_roll: NaiveDate = NaiveDate::from_ymd_opt(year, 9, 20).unwrap();
+    if trade_date < march_roll {
+        NaiveDate::from_ymd_opt(year - 1, 12, 20).unwrap()
+    }
+    else if trade_date < september_roll {
+        NaiveDate::from_ymd_opt(year, 6, 20).unwrap()
+    }
+    else {
+        NaiveDate::from_ymd_opt(year, 12, 20).unwrap()
+    }
+}
+
+// Unadjusted standard maturity of a CDS traded on trade_date (5Y traded on 2024-04-10 matures on 2029-06-20).
+pub fn cds_maturity_date(trade_date: NaiveDate, tenor: &Tenor) -> Result<NaiveDate, TenorDateError> {
+    tenor.add_to_date(cds_roll_date(trade_date), None)
+}
@@ -1,6 +1,10 @@
 pub mod day_counting;
 pub mod time_fractions;
 pub mod calendars;
+pub mod calendar_registry;
 pub mod date_adjusting;
 pub mod tenors;
+pub mod scheduling;
+pub mod settlement;
+pub mod imm;
 pub mod aux_funcs;
\ No newline at end of file
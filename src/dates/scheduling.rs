@@ -1,72 +1,280 @@
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fmt;
 
-use crate::dates::tenors::Tenor;
-use crate::dates::date_adjusting::DateAdjustingMethod;
 use crate::dates::calendars::Calendar;
-use crate::dates::aux_funcs::get_eom;
+use crate::dates::date_adjusting::DateAdjustingMethod;
+use crate::dates::imm::imm_date;
+use crate::dates::tenors::{Tenor, TenorDateError};
+use crate::dates::time_fractions::{DayCountError, TimeFractionCalc};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CouponFrequency {
     Zero,
-    Monthly = Tenor::new("1M"),
-    Quarterly = Tenor::new("3M"),
-    Semiannually = Tenor::new("6M"),
-    Annually = Tenor::new("6M")
+    Monthly,
+    Quarterly,
+    Semiannually,
+    Annually,
+}
+impl CouponFrequency {
+    // Regular period length, None for a single period running from effective date to maturity.
+    pub fn tenor(&self) -> Option<Tenor> {
+        match self {
+            CouponFrequency::Zero => None,
+            CouponFrequency::Monthly => Some(Tenor::months(1)),
+            CouponFrequency::Quarterly => Some(Tenor::months(3)),
+            CouponFrequency::Semiannually => Some(Tenor::months(6)),
+            CouponFrequency::Annually => Some(Tenor::years(1)),
+        }
+    }
+
+    pub fn periods_per_year(&self) -> u32 {
+        match self {
+            CouponFrequency::Zero => 0,
+            CouponFrequency::Monthly => 12,
+            CouponFrequency::Quarterly => 4,
+            CouponFrequency::Semiannually => 2,
+            CouponFrequency::Annually => 1,
+        }
+    }
 }
 
+// Where the irregular period goes when effective date and maturity are not a whole number of periods apart.
+// First stubs roll dates backward from maturity, last stubs roll forward from the effective date. Long stubs merge the
+// short stub with its neighbouring regular period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StubType {
     ShortFirst,
-    ShortLast
+    LongFirst,
+    ShortLast,
+    LongLast,
 }
 
+// Day of the month the schedule rolls on. Standard keeps the day of the date rolled from (clipped to the month end when
+// needed), EndOfMonth rolls on month ends when the date rolled from is a month end. Imm rolls on the third Wednesday and
+// Cds on the 20th of each month the schedule lands on (a maturity tenor lands on them too).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum RollType {
-    Forward,
-    ForwardEOM,
-    Backward,
-    BackwardEOM
+    Standard,
+    EndOfMonth,
+    Imm,
+    Cds,
+}
+impl RollType {
+    fn roll_day(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            RollType::Standard | RollType::EndOfMonth => date,
+            RollType::Imm => imm_date(date.year(), date.month()),
+            RollType::Cds => date.with_day(20).unwrap(),
+        }
+    }
 }
 
-fn get_forward_date(trade_date: NaiveDate, forward_lag: i64, forward_date_adjustment: Option<DateAdjustingMethod>) -> NaiveDate {
-    let forward_date_pre = trade_date + Duration::days(forward_lag);
-    if let fda = Some(forward_date_adjustment) {
-        return fda.adjust(forward_date_pre);
+pub enum Maturity {
+    Date(NaiveDate),
+    Tenor(Tenor),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    MaturityNotAfterEffectiveDate { effective_date: NaiveDate, maturity_date: NaiveDate },
+    Tenor(TenorDateError),
+    DayCount(DayCountError),
+}
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::MaturityNotAfterEffectiveDate { effective_date, maturity_date } => {
+                write!(f, "Maturity {} is not after effective date {}.", maturity_date, effective_date)
+            }
+            ScheduleError::Tenor(error) => write!(f, "{}", error),
+            ScheduleError::DayCount(error) => write!(f, "{}", error),
+        }
+    }
+}
+impl Error for ScheduleError {}
+impl From<TenorDateError> for ScheduleError {
+    fn from(error: TenorDateError) -> Self {
+        ScheduleError::Tenor(error)
     }
-    else {
-        return forward_date_pre;
+}
+impl From<DayCountError> for ScheduleError {
+    fn from(error: DayCountError) -> Self {
+        ScheduleError::DayCount(error)
     }
 }
 
-fn get_effective_date(trade_date: NaiveDate, forward_lag: i64, spot_lag: i64, payment_lag: i64
-                    , effective_date_adjustment: Option<DateAdjustingMethod>
-                    , forward_date_adjustment: Option<DateAdjustingMethod>) -> NaiveDate {
-    let forward_date = get_forward_date(trade_date, forward_lag, forward_date_adjustment);
-    
-    let effective_date_pre = forward_date + Duration::days(spot_lag);
-    if let fda = Some(forward_date_adjustment) {
-        return fda.adjust(forward_date_pre);
-    }
-    else {
-        return forward_date_pre;
+// One accrual period. Reference start and end are the regular period the accrual belongs to (used by ACT/ACT ICMA);
+// they differ from the unadjusted dates only for stubs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchedulePeriod {
+    pub unadjusted_start: NaiveDate,
+    pub unadjusted_end: NaiveDate,
+    pub adjusted_start: NaiveDate,
+    pub adjusted_end: NaiveDate,
+    pub payment_date: NaiveDate,
+    pub reference_start: NaiveDate,
+    pub reference_end: NaiveDate,
+}
+impl SchedulePeriod {
+    pub fn is_stub(&self) -> bool {
+        self.unadjusted_start != self.reference_start || self.unadjusted_end != self.reference_end
     }
 }
 
-pub fn create_maturities_schedule(trade_date: NaiveDate, forward_lag: i64, spot_lag: i64, payment_lag: i64
-                                , maturity_tenor: Tenor, payment_frequency: CouponFrequency
-                                , stub_type: StubType, roll_type: RollType
-                                , coupon_adjustment: DateAdjustingMethod
-                                , effective_date_adjustment: Option<DateAdjustingMethod>
-                                , forward_date_adjustment: Option<DateAdjustingMethod>) -> Vec<NaiveDate> {
-    let effective_date_pre: NaiveDate = trade_date + Duration::days(spot_lag);
-    if let edam = Some(effective_date_adjustment) {
-        let effective_date = edam.adjust(effective_date_pre);
+// Accrual period of a floating (or fixed) leg. The rate resets on the adjusted start and is fixed fixing lag business days
+// before on the fixing calendar. Payment is payment lag business days after the adjusted end on the payment calendar.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccrualPeriod {
+    pub unadjusted_start: NaiveDate,
+    pub unadjusted_end: NaiveDate,
+    pub adjusted_start: NaiveDate,
+    pub adjusted_end: NaiveDate,
+    pub payment_date: NaiveDate,
+    pub reset_date: NaiveDate,
+    pub fixing_date: NaiveDate,
+    pub year_fraction: f64,
+}
+
+pub struct ScheduleDefinition<'a> {
+    pub effective_date: NaiveDate,
+    pub maturity: Maturity,
+    pub frequency: CouponFrequency,
+    pub stub_type: StubType,
+    pub roll_type: RollType,
+    pub adjusting_method: Option<&'a dyn DateAdjustingMethod>,
+}
+impl<'a> ScheduleDefinition<'a> {
+    pub fn new(effective_date: NaiveDate, maturity: Maturity, frequency: CouponFrequency, stub_type: StubType, roll_type: RollType,
+               adjusting_method: Option<&'a dyn DateAdjustingMethod>) -> Self {
+        ScheduleDefinition { effective_date, maturity, frequency, stub_type, roll_type, adjusting_method }
+    }
+
+    // Unadjusted maturity. A maturity tenor is added to the effective date following the roll type.
+    pub fn maturity_date(&self) -> Result<NaiveDate, ScheduleError> {
+        match &self.maturity {
+            Maturity::Date(date) => Ok(*date),
+            Maturity::Tenor(tenor) => {
+                let maturity_date: NaiveDate = tenor.add_to_date_end_of_month(self.effective_date, self.roll_type == RollType::EndOfMonth, None)?;
+                Ok(self.roll_type.roll_day(maturity_date))
+            }
+        }
+    }
+
+    // Unadjusted period boundaries, from effective date to maturity.
+    pub fn unadjusted_dates(&self) -> Result<Vec<NaiveDate>, ScheduleError> {
+        let effective_date: NaiveDate = self.effective_date;
+        let maturity_date: NaiveDate = self.maturity_date()?;
+        if maturity_date <= effective_date {
+            return Err(ScheduleError::MaturityNotAfterEffectiveDate { effective_date, maturity_date });
+        }
+        let period: Tenor = match self.frequency.tenor() {
+            Some(period) => period,
+            None => return Ok(vec![effective_date, maturity_date]),
+        };
+
+        let dates: Vec<NaiveDate> = match self.stub_type {
+            StubType::ShortFirst | StubType::LongFirst => {
+                let mut dates: Vec<NaiveDate> = self.roll_dates(maturity_date, -period, |date| date > effective_date)?;
+                if self.stub_type == StubType::LongFirst && dates.len() > 1 && self.roll(maturity_date, -period, dates.len())? != effective_date {
+                    dates.pop();
+                }
+                dates.push(effective_date);
+                dates.reverse();
+                dates
+            }
+            StubType::ShortLast | StubType::LongLast => {
+                let mut dates: Vec<NaiveDate> = self.roll_dates(effective_date, period, |date| date < maturity_date)?;
+                if self.stub_type == StubType::LongLast && dates.len() > 1 && self.roll(effective_date, period, dates.len())? != maturity_date {
+                    dates.pop();
+                }
+                dates.push(maturity_date);
+                dates
+            }
+        };
+        Ok(dates)
     }
-    else {
-        let effective_date = effective_date_pre;
+
+    pub fn generate(&self) -> Result<Vec<SchedulePeriod>, ScheduleError> {
+        let dates: Vec<NaiveDate> = self.unadjusted_dates()?;
+        let adjusted_dates: Vec<NaiveDate> = match self.adjusting_method {
+            Some(adjuster) => adjuster.adjust_schedule(&dates),
+            None => dates.clone(),
+        };
+        let references: Vec<(NaiveDate, NaiveDate)> = self.reference_periods(&dates)?;
+        let mut periods: Vec<SchedulePeriod> = Vec::with_capacity(dates.len() - 1);
+        for (i, (reference_start, reference_end)) in references.into_iter().enumerate() {
+            periods.push(SchedulePeriod {
+                unadjusted_start: dates[i],
+                unadjusted_end: dates[i + 1],
+                adjusted_start: adjusted_dates[i],
+                adjusted_end: adjusted_dates[i + 1],
+                payment_date: adjusted_dates[i + 1],
+                reference_start,
+                reference_end,
+            });
+        }
+        Ok(periods)
+    }
+
+    // Dates are always rolled from the anchor (never from the previous date) so that month end clipping does not drift.
+    fn roll(&self, anchor: NaiveDate, period: Tenor, count: usize) -> Result<NaiveDate, ScheduleError> {
+        let end_of_month: bool = self.roll_type == RollType::EndOfMonth;
+        let total: Tenor = i32::try_from(count).ok().and_then(|count| period.checked_mul(count))
+            .ok_or_else(|| TenorDateError::OutOfRange { date: anchor, tenor: period.to_string() })?;
+        let date: NaiveDate = total.add_to_date_end_of_month(anchor, end_of_month, None)?;
+        Ok(self.roll_type.roll_day(date))
+    }
+
+    fn roll_dates<F: Fn(NaiveDate) -> bool>(&self, anchor: NaiveDate, period: Tenor, keep_rolling: F) -> Result<Vec<NaiveDate>, ScheduleError> {
+        let mut dates: Vec<NaiveDate> = vec![anchor];
+        let mut count: usize = 1;
+        loop {
+            let date: NaiveDate = self.roll(anchor, period, count)?;
+            if !keep_rolling(date) {
+                return Ok(dates);
+            }
+            dates.push(date);
+            count += 1;
+        }
+    }
+
+    // Regular period ending at the end of the first period (first stubs) or starting at the start of the last period (last
+    // stubs), rolled from the same anchor as the schedule. Other periods are their own reference.
+    fn reference_periods(&self, dates: &[NaiveDate]) -> Result<Vec<(NaiveDate, NaiveDate)>, ScheduleError> {
+        let mut references: Vec<(NaiveDate, NaiveDate)> = dates.windows(2).map(|w| (w[0], w[1])).collect();
+        let period: Tenor = match self.frequency.tenor() {
+            Some(period) => period,
+            None => return Ok(references),
+        };
+        let regular_count: usize = dates.len() - 1;
+        match self.stub_type {
+            StubType::ShortFirst | StubType::LongFirst => {
+                references[0].0 = self.roll(dates[dates.len() - 1], -period, regular_count)?;
+            }
+            StubType::ShortLast | StubType::LongLast => {
+                references[regular_count - 1].1 = self.roll(dates[0], period, regular_count)?;
+            }
+        }
+        Ok(references)
     }
-    
-    let maturity_date_pre_eom_roll_adj: NaiveDate = maturity_tenor.add_to_date(effective_date, None);
-    if !end_of_month_roll {
 
-    } else {
-        let effective_date_is_eom: bool = get_eom(effective_date) == effective_date;
+    // Year fractions accrue between adjusted dates. Reference periods are always unadjusted: regular periods are their own
+    // reference period, stubs use the regular period next to them.
+    pub fn accrual_periods(&self, payment_lag: u32, payment_calendar: &Calendar, fixing_lag: u32, fixing_calendar: &Calendar,
+                           time_fraction_calc: &dyn TimeFractionCalc) -> Result<Vec<AccrualPeriod>, ScheduleError> {
+        let periods: Vec<SchedulePeriod> = self.generate()?;
+        periods.iter().map(|period| {
+            Ok(AccrualPeriod {
+                unadjusted_start: period.unadjusted_start,
+                unadjusted_end: period.unadjusted_end,
+                adjusted_start: period.adjusted_start,
+                adjusted_end: period.adjusted_end,
+                payment_date: payment_calendar.add_business_days(period.adjusted_end, payment_lag),
+                reset_date: period.adjusted_start,
+                fixing_date: fixing_calendar.substract_business_days(period.adjusted_start, fixing_lag),
+                year_fraction: time_fraction_calc.try_time_fraction_with_reference(period.adjusted_start, period.adjusted_end, period.reference_start, period.reference_end)?,
+            })
+        }).collect()
     }
-}
\ No newline at end of file
+}
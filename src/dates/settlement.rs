@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::dates::calendars::Calendar;
+
+// Settlement (spot) date: lag business days after trade date on lag_calendar, then moved forward until it is a business
+// day on both lag_calendar and adjustment_calendar. With a lag of 0, a trade date that is a holiday settles on the next business day.
+// For FX spot, lag_calendar joins the calendars of both currencies (see Calendar::combine) and adjustment_calendar is
+// the USD calendar, so that spot never falls on a USD holiday.
+pub struct SettlementRule<'a> {
+    pub lag: u32,
+    lag_calendar: &'a Calendar,
+    adjustment_calendar: Option<&'a Calendar>,
+}
+impl<'a> SettlementRule<'a> {
+    pub fn new(lag: u32, lag_calendar: &'a Calendar, adjustment_calendar: Option<&'a Calendar>) -> Self {
+        SettlementRule { lag, lag_calendar, adjustment_calendar }
+    }
+
+    pub fn settlement_date(&self, trade_date: NaiveDate) -> NaiveDate {
+        let mut settlement_date: NaiveDate = self.lag_calendar.add_business_days(trade_date, self.lag);
+        while !self.lag_calendar.is_business_day(settlement_date)
+            || !self.adjustment_calendar.is_none_or(|calendar| calendar.is_business_day(settlement_date)) {
+            settlement_date = self.lag_calendar.add_business_days(settlement_date, 1);
+        }
+        settlement_date
+    }
+}
+
+pub fn spot_date(trade_date: NaiveDate, lag: u32, lag_calendar: &Calendar, adjustment_calendar: Option<&Calendar>) -> NaiveDate {
+    SettlementRule::new(lag, lag_calendar, adjustment_calendar).settlement_date(trade_date)
+}
+
+// Settlement lags by currency pair or instrument code, with a default for codes without a specific lag.
+// Codes are matched ignoring case and '/'. Currency pairs set with set_currency_pair_lag are also matched in both
+// directions ("usd/cad" == "CADUSD"), other codes are not.
+pub struct SettlementLags {
+    pub default_lag: u32,
+    lags: HashMap<String, u32>,
+}
+impl SettlementLags {
+    pub fn new(default_lag: u32) -> Self {
+        SettlementLags { default_lag, lags: HashMap::new() }
+    }
+
+    // FX spot: T+2, except T+1 for USD against CAD, TRY, PHP, RUB and KZT.
+    pub fn fx_spot() -> Self {
+        let mut lags = SettlementLags::new(2);
+        for pair in ["USDCAD", "USDTRY", "USDPHP", "USDRUB", "USDKZT"] {
+            lags.set_currency_pair_lag(pair, 1);
+        }
+        lags
+    }
+
+    fn key(code: &str) -> String {
+        code.trim().to_uppercase().replace('/', "")
+    }
+
+    pub fn set_lag(&mut self, code: &str, lag: u32) {
+        self.lags.insert(SettlementLags::key(code), lag);
+    }
+
+    // Panics if pair is not made of two 3 letter currency codes.
+    pub fn set_currency_pair_lag(&mut self, pair: &str, lag: u32) {
+        let key: String = SettlementLags::key(pair);
+        if key.len() != 6 || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+            panic!("'{}' is not a currency pair.", pair);
+        }
+        let reversed: String = format!("{}{}", &key[3..], &key[..3]);
+        self.lags.insert(reversed, lag);
+        self.lags.insert(key, lag);
+    }
+
+    pub fn get_lag(&self, code: &str) -> u32 {
+        self.lags.get(&SettlementLags::key(code)).copied().unwrap_or(self.default_lag)
+    }
+
+    pub fn rule<'a>(&self, code: &str, lag_calendar: &'a Calendar, adjustment_calendar: Option<&'a Calendar>) -> SettlementRule<'a> {
+        SettlementRule::new(self.get_lag(code), lag_calendar, adjustment_calendar)
+    }
+}
@@ -1,115 +1,461 @@
 use chrono::{Duration, Months, NaiveDate};
-use std::collections::HashMap;
-use lazy_static::lazy_static;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops;
+use std::str::FromStr;
 
+use crate::dates::aux_funcs::get_eom;
+use crate::dates::calendars::Calendar;
 use crate::dates::date_adjusting::DateAdjustingMethod;
 
 
-lazy_static!{
-    static ref TENOR_MAP: HashMap<&'static str, (u8, char)> = {
-        let mut map = HashMap::new();
-        
-        map.insert("1D", (1, 'D'));
-        map.insert("2D", (2, 'D'));
-        map.insert("1W", (1, 'W'));
-        map.insert("2W", (2, 'W'));
-        map.insert("3W", (3, 'W'));
-        map.insert("1M", (1, 'M'));
-        map.insert("2M", (2, 'M'));
-        map.insert("3M", (3, 'M'));
-        map.insert("4M", (4, 'M'));
-        map.insert("5M", (5, 'M'));
-        map.insert("6M", (6, 'M'));
-        map.insert("7M", (7, 'M'));
-        map.insert("8M", (8, 'M'));
-        map.insert("9M", (9, 'M'));
-        map.insert("10M", (10, 'M'));
-        map.insert("11M", (11, 'M'));
-        map.insert("12M", (12, 'M'));
-        map.insert("1Y", (12, 'M'));
-        map.insert("18M", (18, 'M'));        
-        map.insert("1Y6M", (18, 'M'));
-        map.insert("2Y", (2, 'Y'));
-        map.insert("3Y", (3, 'Y'));
-        map.insert("4Y", (4, 'Y'));
-        map.insert("5Y", (5, 'Y'));
-        map.insert("10Y", (10, 'Y'));
-        map.insert("20Y", (20, 'Y'));
-        map.insert("25Y", (25, 'Y'));
-        map.insert("30Y", (30, 'Y'));
-        map.insert("35Y", (35, 'Y'));
-        map.insert("40Y", (40, 'Y'));
-        map.insert("45Y", (45, 'Y'));
-        map.insert("50Y", (50, 'Y'));
-        
-        map
-    };
-    static ref TENOR_UNIT_FUNC_MAP: HashMap<char, fn(&Tenor, NaiveDate, u8) -> NaiveDate> = {
-        let mut map = HashMap::new();
-        map.insert('D', Tenor::add_days as fn(&Tenor, NaiveDate, u8) -> NaiveDate);
-        map.insert('W', Tenor::add_weeks as fn(&Tenor, NaiveDate, u8) -> NaiveDate);
-        map.insert('M', Tenor::add_months as fn(&Tenor, NaiveDate, u8) -> NaiveDate);
-        map.insert('Y', Tenor::add_years as fn(&Tenor, NaiveDate, u8) -> NaiveDate);
-        map
-    };
+// Money market tenors. All of them last one business day except spot week, which lasts one week.
+// They differ in when they start: today (ON), tomorrow (TN) or spot (SN, SW).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MoneyMarketCode {
+    Overnight,
+    TomorrowNext,
+    SpotNext,
+    SpotWeek,
+}
+impl MoneyMarketCode {
+    // Business days from trade date to start date.
+    pub fn start_lag(&self, spot_lag: u32) -> u32 {
+        match self {
+            MoneyMarketCode::Overnight => 0,
+            MoneyMarketCode::TomorrowNext => 1,
+            MoneyMarketCode::SpotNext | MoneyMarketCode::SpotWeek => spot_lag,
+        }
+    }
 }
 
+// Tenor grammar (case insensitive, surrounding spaces ignored):
+//     tenor  := [sign] (code | period ([sign] period)*)
+//     sign   := "+" | "-"
+//     code   := "ON" | "TN" | "SN" | "SW"
+//     period := digits unit
+//     unit   := "Y" | "M" | "W" | "D" | "B" | "BD"
+// Each unit can appear once ("1Y6M", "2W3D", "-3M", "5BD"). The leading sign applies to the whole tenor, and a sign
+// inside it applies to the next period only ("1M-1D" is one month minus one day, "-1M-1D" is minus one month plus one day).
+//
+// Two tenors are equal if they have the same months (12M == 1Y), days (7D == 1W), business days and money market code.
+// Display keeps the periods as built ("12M" prints "12M"), use normalized to get years and weeks out of months and days.
+#[derive(Clone, Copy, Debug)]
 pub struct Tenor {
-    value: u8,
-    unit: char
+    years: i32,
+    months: i32,
+    weeks: i32,
+    days: i32,
+    business_days: i32,
+    code: Option<MoneyMarketCode>,
 }
 
 impl Tenor {
+    pub fn new(years: i32, months: i32, weeks: i32, days: i32, business_days: i32) -> Self {
+        Tenor { years, months, weeks, days, business_days, code: None }
+    }
+
+    pub fn years(years: i32) -> Self {
+        Tenor::new(years, 0, 0, 0, 0)
+    }
+
+    pub fn months(months: i32) -> Self {
+        Tenor::new(0, months, 0, 0, 0)
+    }
+
+    pub fn weeks(weeks: i32) -> Self {
+        Tenor::new(0, 0, weeks, 0, 0)
+    }
+
+    pub fn days(days: i32) -> Self {
+        Tenor::new(0, 0, 0, days, 0)
+    }
+
+    pub fn business_days(business_days: i32) -> Self {
+        Tenor::new(0, 0, 0, 0, business_days)
+    }
+
+    pub fn from_code(code: MoneyMarketCode) -> Self {
+        let tenor: Tenor = match code {
+            MoneyMarketCode::SpotWeek => Tenor::weeks(1),
+            _ => Tenor::business_days(1),
+        };
+        Tenor { code: Some(code), ..tenor }
+    }
+
+    pub fn get_years(&self) -> i32 {
+        self.years
+    }
+
+    pub fn get_months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn get_weeks(&self) -> i32 {
+        self.weeks
+    }
+
+    pub fn get_days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn get_business_days(&self) -> i32 {
+        self.business_days
+    }
+
+    pub fn get_code(&self) -> Option<MoneyMarketCode> {
+        self.code
+    }
+
     pub fn from_str_opt(input: &str) -> Option<Self> {
-        let tenor_map = &*TENOR_MAP;
-        let input = input.trim().to_uppercase();
-        
-        if let Some(&(value, unit)) = tenor_map.get(&input[..]) {
-            Some(Tenor {
-                value,
-                unit
-            })
+        input.parse().ok()
+    }
+
+    pub fn total_months(&self) -> i64 {
+        12 * self.years as i64 + self.months as i64
+    }
+
+    pub fn total_days(&self) -> i64 {
+        7 * self.weeks as i64 + self.days as i64
+    }
+
+    // Months go to years and days go to weeks (15M => 1Y3M, 10D => 1W3D, 1Y-3M => 9M). Money market code is kept.
+    pub fn normalized(&self) -> Tenor {
+        let (total_months, total_days) = (self.total_months(), self.total_days());
+        Tenor {
+            years: (total_months / 12) as i32,
+            months: (total_months % 12) as i32,
+            weeks: (total_days / 7) as i32,
+            days: (total_days % 7) as i32,
+            business_days: self.business_days,
+            code: self.code,
+        }
+    }
+
+    fn periods(&self) -> [(i32, &'static str); 5] {
+        [(self.years, "Y"), (self.months, "M"), (self.weeks, "W"), (self.days, "D"), (self.business_days, "B")]
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(code) = self.code {
+            let name: &str = match code {
+                MoneyMarketCode::Overnight => "ON",
+                MoneyMarketCode::TomorrowNext => "TN",
+                MoneyMarketCode::SpotNext => "SN",
+                MoneyMarketCode::SpotWeek => "SW",
+            };
+            return write!(f, "{}", name);
+        }
+        let periods: Vec<(i32, &str)> = self.periods().into_iter().filter(|(amount, _)| *amount != 0).collect();
+        if periods.is_empty() {
+            return write!(f, "0D");
+        }
+        // Leading sign is the sign of the first period, following periods are written relative to it.
+        let sign: i64 = if periods[0].0 < 0 { -1 } else { 1 };
+        if sign < 0 {
+            write!(f, "-")?;
+        }
+        for (amount, unit) in periods {
+            let relative: i64 = sign * amount as i64;
+            if relative < 0 {
+                write!(f, "-")?;
+            }
+            write!(f, "{}{}", relative.abs(), unit)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Tenor {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_months() == other.total_months()
+            && self.total_days() == other.total_days()
+            && self.business_days == other.business_days
+            && self.code == other.code
+    }
+}
+impl Eq for Tenor {}
+
+impl Hash for Tenor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_months().hash(state);
+        self.total_days().hash(state);
+        self.business_days.hash(state);
+        self.code.hash(state);
+    }
+}
+
+// Shortest and longest number of days in a number of consecutive months, starting at the 1st of a month.
+fn days_in_months_range(months: i64) -> (i64, i64) {
+    if months < 0 {
+        let (min_days, max_days) = days_in_months_range(-months);
+        return (-max_days, -min_days);
+    }
+    let months: u32 = u32::try_from(months).unwrap_or(u32::MAX);
+    let mut range: (i64, i64) = (i64::MAX, i64::MIN);
+    for start_month in 0..48 {
+        let start: NaiveDate = NaiveDate::from_ymd_opt(2001 + start_month / 12, 1 + start_month as u32 % 12, 1).unwrap();
+        let days: i64 = match start.checked_add_months(Months::new(months)) {
+            Some(end) => (end - start).num_days(),
+            None => i64::MAX,
+        };
+        range = (range.0.min(days), range.1.max(days));
+    }
+    range
+}
+
+// Tenors are ordered only when the order does not depend on the start date: 1M < 5W and 1Y > 360D, but 1M and 30D are
+// not comparable (a month lasts 28 to 31 days). Business days are only compared when the rest of both tenors is equal,
+// and tenors with different money market codes are not comparable.
+impl PartialOrd for Tenor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.code != other.code {
+            return None;
+        }
+        let months_difference: i64 = self.total_months() - other.total_months();
+        let days_difference: i64 = self.total_days() - other.total_days();
+        if months_difference == 0 && days_difference == 0 {
+            return Some(self.business_days.cmp(&other.business_days));
+        }
+        if self.business_days != other.business_days {
+            return None;
+        }
+        let (min_days, max_days) = days_in_months_range(months_difference);
+        if min_days.saturating_add(days_difference) > 0 {
+            Some(Ordering::Greater)
+        } else if max_days.saturating_add(days_difference) < 0 {
+            Some(Ordering::Less)
         } else {
-            if input.len() < 2 {
-                return None;
+            None
+        }
+    }
+}
+
+// Arithmetic works period by period and drops the money market code. The checked versions return None if a period
+// overflows, the operators panic.
+impl Tenor {
+    pub fn checked_add(&self, rhs: Tenor) -> Option<Tenor> {
+        Some(Tenor::new(
+            self.years.checked_add(rhs.years)?,
+            self.months.checked_add(rhs.months)?,
+            self.weeks.checked_add(rhs.weeks)?,
+            self.days.checked_add(rhs.days)?,
+            self.business_days.checked_add(rhs.business_days)?,
+        ))
+    }
+
+    pub fn checked_neg(&self) -> Option<Tenor> {
+        self.checked_mul(-1)
+    }
+
+    pub fn checked_sub(&self, rhs: Tenor) -> Option<Tenor> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, rhs: i32) -> Option<Tenor> {
+        Some(Tenor::new(
+            self.years.checked_mul(rhs)?,
+            self.months.checked_mul(rhs)?,
+            self.weeks.checked_mul(rhs)?,
+            self.days.checked_mul(rhs)?,
+            self.business_days.checked_mul(rhs)?,
+        ))
+    }
+}
+
+impl ops::Add<Tenor> for Tenor {
+    type Output = Tenor;
+    fn add(self, rhs: Tenor) -> Self::Output {
+        self.checked_add(rhs).unwrap_or_else(|| panic!("Adding tenors {} and {} overflows.", self, rhs))
+    }
+}
+
+impl ops::Neg for Tenor {
+    type Output = Tenor;
+    fn neg(self) -> Self::Output {
+        self.checked_neg().unwrap_or_else(|| panic!("Negating tenor {} overflows.", self))
+    }
+}
+
+impl ops::Sub<Tenor> for Tenor {
+    type Output = Tenor;
+    fn sub(self, rhs: Tenor) -> Self::Output {
+        self.checked_sub(rhs).unwrap_or_else(|| panic!("Substracting tenor {} from {} overflows.", rhs, self))
+    }
+}
+
+impl ops::Mul<i32> for Tenor {
+    type Output = Tenor;
+    fn mul(self, rhs: i32) -> Self::Output {
+        self.checked_mul(rhs).unwrap_or_else(|| panic!("Multiplying tenor {} by {} overflows.", self, rhs))
+    }
+}
+
+impl ops::Mul<Tenor> for i32 {
+    type Output = Tenor;
+    fn mul(self, rhs: Tenor) -> Self::Output {
+        rhs * self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTenorError {
+    pub input: String,
+    pub reason: String,
+}
+impl fmt::Display for ParseTenorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid tenor '{}': {}.", self.input, self.reason)
+    }
+}
+impl Error for ParseTenorError {}
+
+impl FromStr for Tenor {
+    type Err = ParseTenorError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| ParseTenorError { input: s.to_string(), reason };
+
+        let input: String = s.trim().to_uppercase();
+        let (sign, body): (i32, &str) = match input.strip_prefix('-') {
+            Some(body) => (-1, body),
+            None => (1, input.strip_prefix('+').unwrap_or(&input)),
+        };
+        if body.is_empty() {
+            return Err(error("empty tenor".to_string()));
+        }
+
+        let code: Option<MoneyMarketCode> = match body {
+            "ON" => Some(MoneyMarketCode::Overnight),
+            "TN" => Some(MoneyMarketCode::TomorrowNext),
+            "SN" => Some(MoneyMarketCode::SpotNext),
+            "SW" => Some(MoneyMarketCode::SpotWeek),
+            _ => None,
+        };
+        if let Some(code) = code {
+            if sign < 0 {
+                return Err(error("money market tenors can not be negative".to_string()));
+            }
+            return Ok(Tenor::from_code(code));
+        }
+
+        let mut amounts: [Option<i32>; 5] = [None; 5]; // Y, M, W, D, B
+        let mut rest: &str = body;
+        while !rest.is_empty() {
+            let mut period_sign: i32 = sign;
+            if rest.len() < body.len() {
+                if let Some(stripped) = rest.strip_prefix('-') {
+                    period_sign = -sign;
+                    rest = stripped;
+                } else if let Some(stripped) = rest.strip_prefix('+') {
+                    rest = stripped;
+                }
             }
-            let value_part = &input[..input.len() - 1];
-            let unit_part = input.chars().last()?;
+            let digits_end: usize = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            if digits_end == 0 {
+                return Err(error(format!("expected a number before '{}'", rest)));
+            }
+            let amount: i32 = rest[..digits_end].parse().map_err(|_| error(format!("amount '{}' is too large", &rest[..digits_end])))?;
+            rest = &rest[digits_end..];
 
-            if let Ok(value) = value_part.parse::<u8>() {
-                Some(Tenor { value, unit: unit_part })
-            } else {
-                None
+            let unit_end: usize = rest.find(|c: char| c.is_ascii_digit() || c == '-' || c == '+').unwrap_or(rest.len());
+            let position: usize = match &rest[..unit_end] {
+                "Y" => 0,
+                "M" => 1,
+                "W" => 2,
+                "D" => 3,
+                "B" | "BD" => 4,
+                "" => return Err(error(format!("missing unit after '{}'", amount))),
+                unit => return Err(error(format!("unknown unit '{}', admitted units are 'Y', 'M', 'W', 'D' and 'B' or 'BD'", unit))),
+            };
+            if amounts[position].is_some() {
+                return Err(error(format!("unit '{}' appears more than once", &rest[..unit_end])));
             }
+            amounts[position] = Some(period_sign * amount);
+            rest = &rest[unit_end..];
         }
+        let [years, months, weeks, days, business_days] = amounts.map(|amount| amount.unwrap_or(0));
+        Ok(Tenor::new(years, months, weeks, days, business_days))
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TenorDateError {
+    // Result is outside of the dates supported by chrono.
+    OutOfRange { date: NaiveDate, tenor: String },
+    // Tenors with business days need a calendar (use add_to_date_with_calendar).
+    MissingCalendar { tenor: String },
+}
+impl fmt::Display for TenorDateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TenorDateError::OutOfRange { date, tenor } => write!(f, "Adding tenor {} to {} gives a date out of range.", tenor, date),
+            TenorDateError::MissingCalendar { tenor } => write!(f, "Tenor {} has business days, a calendar is needed.", tenor),
+        }
+    }
+}
+impl Error for TenorDateError {}
+
 impl Tenor {
-    pub fn add_to_date(&self, date: NaiveDate, adjusting_method: Option<&dyn DateAdjustingMethod>) -> NaiveDate {
-        let future_date: NaiveDate = if let Some(func) = TENOR_UNIT_FUNC_MAP.get(&self.unit) {
-            func(self, date, self.value)
-        } else {
-            panic!("Unexpected value. Admitted values are 'D', 'W', 'M' and 'Y'.")
-        };
+    // Months and years are added together first, then weeks and days, and the adjusting method is applied at the end.
+    pub fn add_to_date(&self, date: NaiveDate, adjusting_method: Option<&dyn DateAdjustingMethod>) -> Result<NaiveDate, TenorDateError> {
+        self.add_to_date_end_of_month(date, false, adjusting_method)
+    }
+
+    // With end_of_month, month and year periods added to a month end land on a month end (2024-04-30 + 1M = 2024-05-31,
+    // 2023-02-28 + 1Y = 2024-02-29). Weeks and days are added after that.
+    pub fn add_to_date_end_of_month(&self, date: NaiveDate, end_of_month: bool, adjusting_method: Option<&dyn DateAdjustingMethod>) -> Result<NaiveDate, TenorDateError> {
+        if self.business_days != 0 {
+            return Err(TenorDateError::MissingCalendar { tenor: self.to_string() });
+        }
+        let future_date: NaiveDate = self.add_calendar_periods(date, end_of_month)?;
         if let Some(adjuster) = adjusting_method {
-            return adjuster.adjust(future_date);
+            Ok(adjuster.adjust(future_date))
         }
         else {
-            return future_date;
+            Ok(future_date)
         }
     }
-    fn add_days(&self, date: NaiveDate, amount: u8) -> NaiveDate {
-        date + Duration::days(amount as i64)
-    }
-    fn add_weeks(&self, date: NaiveDate, amount: u8) -> NaiveDate {
-        self.add_days(date, amount * 7)
+
+    // Same as add_to_date, with business days added on calendar after months, years, weeks and days.
+    pub fn add_to_date_with_calendar(&self, date: NaiveDate, calendar: &Calendar, end_of_month: bool, adjusting_method: Option<&dyn DateAdjustingMethod>) -> Result<NaiveDate, TenorDateError> {
+        let mut future_date: NaiveDate = self.add_calendar_periods(date, end_of_month)?;
+        let out_of_range = || TenorDateError::OutOfRange { date, tenor: self.to_string() };
+        if self.business_days != 0 {
+            future_date = if self.business_days > 0 {
+                calendar.checked_add_business_days(future_date, self.business_days as u32)
+            } else {
+                calendar.checked_substract_business_days(future_date, self.business_days.unsigned_abs())
+            }.ok_or_else(out_of_range)?;
+        }
+        if let Some(adjuster) = adjusting_method {
+            Ok(adjuster.adjust(future_date))
+        }
+        else {
+            Ok(future_date)
+        }
     }
-    fn add_months(&self, date: NaiveDate, amount: u8) -> NaiveDate {
-        date + Months::new(amount as u32)
+
+    fn add_calendar_periods(&self, date: NaiveDate, end_of_month: bool) -> Result<NaiveDate, TenorDateError> {
+        let out_of_range = || TenorDateError::OutOfRange { date, tenor: self.to_string() };
+        let total_months: i64 = self.total_months();
+        let total_days: i64 = self.total_days();
+        let mut with_months: NaiveDate = self.add_months(date, total_months).ok_or_else(out_of_range)?;
+        if end_of_month && total_months != 0 && date == get_eom(date) {
+            with_months = get_eom(with_months);
+        }
+        with_months.checked_add_signed(Duration::days(total_days)).ok_or_else(out_of_range)
     }
-    fn add_years(&self, date: NaiveDate, amount: u8) -> NaiveDate {
-        self.add_months(date, amount * 12)
+
+    fn add_months(&self, date: NaiveDate, amount: i64) -> Option<NaiveDate> {
+        let months: Months = Months::new(u32::try_from(amount.unsigned_abs()).ok()?);
+        if amount >= 0 {
+            date.checked_add_months(months)
+        } else {
+            date.checked_sub_months(months)
+        }
     }
 }
-
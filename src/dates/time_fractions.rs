@@ -1,29 +1,78 @@
-use chrono::{Datelike, NaiveDate};
-use crate::dates::day_counting::{DayCounter, ActualCounter};
-use crate::dates::aux_funcs::is_leap_year;
+use chrono::{Datelike, Months, NaiveDate};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::dates::day_counting::{DayCounter, ActualCounter, BusinessDayCounter, NoLeapCounter, Days30BondCounter, Days30Counter, Days30ECounter, Days30EISDACounter, Days30EPlusCounter, Days30GermanCounter, Days30UCounter};
+use crate::dates::calendars::Calendar;
+use crate::dates::aux_funcs::{count_leap_days, get_eom, is_leap_year};
 
 #[allow(dead_code)]
 pub trait TimeFractionCalc {
     fn time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64;
-    fn time_fraction_vector(&self, start_date: NaiveDate, end_dates: &Vec<NaiveDate>) -> Vec<f64> {
+    fn time_fraction_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<f64> {
         end_dates.iter().map(|end_date| self.time_fraction(start_date, *end_date)).collect()
     }
+    // Reference period is the regular coupon period containing (or next to) [start_date, end_date]. Only used by conventions that need it (ACT/ACT ICMA).
+    fn time_fraction_with_reference(&self, start_date: NaiveDate, end_date: NaiveDate, _reference_start: NaiveDate, _reference_end: NaiveDate) -> f64 {
+        self.time_fraction(start_date, end_date)
+    }
+    // Fallible versions for conventions that can not give a year fraction for some inputs (ACT/ACT ICMA without a
+    // frequency needs a regular reference period). time_fraction and time_fraction_with_reference panic on those inputs.
+    fn try_time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> Result<f64, DayCountError> {
+        Ok(self.time_fraction(start_date, end_date))
+    }
+    fn try_time_fraction_with_reference(&self, start_date: NaiveDate, end_date: NaiveDate, reference_start: NaiveDate, reference_end: NaiveDate) -> Result<f64, DayCountError> {
+        Ok(self.time_fraction_with_reference(start_date, end_date, reference_start, reference_end))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DayCountError {
+    // Conventions counting business days (BUS/252) need a calendar (use DayCountConvention::bind).
+    MissingCalendar { convention: DayCountConvention },
+    // ACT/ACT ICMA needs the coupon frequency (use DayCountConvention::with_frequency).
+    MissingFrequency { convention: DayCountConvention },
+    // ACT/ACT ICMA frequencies must divide 12.
+    InvalidFrequency { frequency: u32 },
+    // ACT/ACT ICMA without a frequency infers it from the reference period, so it needs one.
+    MissingReferencePeriod,
+    // Reference periods must be a whole number of months dividing 12 when the frequency is inferred from them.
+    InvalidReferencePeriod { reference_start: NaiveDate, reference_end: NaiveDate },
 }
+impl fmt::Display for DayCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayCountError::MissingCalendar { convention } => write!(f, "{} needs a calendar.", convention),
+            DayCountError::MissingFrequency { convention } => write!(f, "{} needs a coupon frequency.", convention),
+            DayCountError::InvalidFrequency { frequency } => write!(f, "Coupon frequency must be 1, 2, 3, 4, 6 or 12, got {}.", frequency),
+            DayCountError::MissingReferencePeriod => write!(f, "ACT/ACT ICMA without a frequency needs a reference period."),
+            DayCountError::InvalidReferencePeriod { reference_start, reference_end } => {
+                write!(f, "Reference period from {} to {} is not a regular coupon period.", reference_start, reference_end)
+            }
+        }
+    }
+}
+impl Error for DayCountError {}
 
-pub struct FixedBaseTimeFractionCalc {
-    pub day_counter: Box<dyn DayCounter>,
+pub struct FixedBaseTimeFractionCalc<'a> {
+    pub day_counter: Box<dyn DayCounter + 'a>,
     pub base: f64,
 }
-impl TimeFractionCalc for FixedBaseTimeFractionCalc{
+impl<'a> TimeFractionCalc for FixedBaseTimeFractionCalc<'a>{
     fn time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
         self.day_counter.day_count(start_date, end_date) as f64 / self.base
     }
-    fn time_fraction_vector(&self, start_date: NaiveDate, end_dates: &Vec<NaiveDate>) -> Vec<f64> {
+    fn time_fraction_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<f64> {
         let days = self.day_counter.day_count_vector(start_date, end_dates);
         days.iter().map(|&x| x as f64 / self.base).collect()  // use intrinsics here
     }
 }
 
+// BUS/252: business days in [start_date, end_date) on the calendar over 252.
+pub fn business_252(calendar: &Calendar) -> FixedBaseTimeFractionCalc<'_> {
+    FixedBaseTimeFractionCalc{day_counter: Box::new(BusinessDayCounter::new(calendar)), base: 252.0}
+}
+
 pub struct ActualActualISDA {
     day_counter: ActualCounter
 }
@@ -40,9 +89,9 @@ impl TimeFractionCalc for ActualActualISDA {
     
         let mut year_fraction = 0.0;
     
-        // Days from start date to the end of the start year
-        let end_of_start_year = NaiveDate::from_ymd_opt(start_year, 12, 31).unwrap();
-        year_fraction += self.day_counter.day_count(start_date, end_of_start_year) as f64 / start_year_days as f64;
+        // Days from start date to the end of the start year, 31st of December included
+        let start_of_next_year = NaiveDate::from_ymd_opt(start_year + 1, 1, 1).unwrap();
+        year_fraction += self.day_counter.day_count(start_date, start_of_next_year) as f64 / start_year_days as f64;
     
         // Days from the beginning of the end year to the end date
         let start_of_end_year = NaiveDate::from_ymd_opt(end_year, 1, 1).unwrap();
@@ -64,4 +113,274 @@ impl ActualActualISDA {
             day_counter: ActualCounter{}
         }
     }    
-}
\ No newline at end of file
+}
+
+impl Default for ActualActualISDA {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ACT/365L (ISMA year): base is 366 if a 29th of February falls in (start_date, end_date] for annual coupons,
+// or if end_date is in a leap year otherwise.
+pub struct Actual365L {
+    pub annual: bool,
+}
+impl Actual365L {
+    pub fn new(annual: bool) -> Self {
+        Actual365L { annual }
+    }
+
+    fn base(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
+        let leap: bool = if self.annual {
+            count_leap_days(start_date, end_date) > 0
+        } else {
+            end_date.leap_year()
+        };
+        if leap { 366.0 } else { 365.0 }
+    }
+}
+impl TimeFractionCalc for Actual365L {
+    fn time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
+        ActualCounter.day_count(start_date, end_date) as f64 / self.base(start_date, end_date)
+    }
+    fn time_fraction_vector(&self, start_date: NaiveDate, end_dates: &[NaiveDate]) -> Vec<f64> {
+        let days = ActualCounter.day_count_vector(start_date, end_dates);
+        days.iter().zip(end_dates).map(|(&x, end_date)| x as f64 / self.base(start_date, *end_date)).collect()
+    }
+}
+
+// ACT/ACT AFB (French): whole years counted back from end_date, plus the remaining days over 366 if they contain a 29th of February, 365 otherwise.
+pub struct ActualActualAFB;
+impl TimeFractionCalc for ActualActualAFB {
+    fn time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
+        if end_date < start_date {
+            return -self.time_fraction(end_date, start_date);
+        }
+        let mut whole_years: i32 = 0;
+        let mut stub_end: NaiveDate = end_date;
+        loop {
+            // A period ending on the 29th of February rolls back to the 28th, and one ending on the 28th of a leap year rolls back to the 29th.
+            let previous: NaiveDate = match stub_end.with_year(stub_end.year() - 1) {
+                Some(date) if date.month() == 2 && date.day() == 28 && date.leap_year() => date.succ_opt().unwrap(),
+                Some(date) => date,
+                None => NaiveDate::from_ymd_opt(stub_end.year() - 1, 2, 28).unwrap(),
+            };
+            if previous < start_date {
+                break;
+            }
+            whole_years += 1;
+            stub_end = previous;
+        }
+        let base: f64 = if count_leap_days(start_date.pred_opt().unwrap(), stub_end.pred_opt().unwrap()) > 0 { 366.0 } else { 365.0 };
+        whole_years as f64 + ActualCounter.day_count(start_date, stub_end) as f64 / base
+    }
+}
+
+// ACT/ACT ICMA: days in the period over days in the quasi coupon period times coupon frequency. Stubs are split across quasi coupon
+// periods rolled from the reference period. Without a reference period, [end_date - 12/frequency months, end_date] is used (short or long front stub).
+// If frequency is None it is inferred from the reference period length, so a reference period is required. Frequency must divide 12
+// (1, 2, 3, 4, 6 or 12). With end_of_month, quasi coupon dates rolled from a month end are month ends.
+pub struct ActualActualICMA {
+    pub frequency: Option<u32>,
+    pub end_of_month: bool,
+}
+impl ActualActualICMA {
+    pub fn new(frequency: Option<u32>, end_of_month: bool) -> Result<Self, DayCountError> {
+        if let Some(frequency) = frequency {
+            if !matches!(frequency, 1 | 2 | 3 | 4 | 6 | 12) {
+                return Err(DayCountError::InvalidFrequency { frequency });
+            }
+        }
+        Ok(ActualActualICMA { frequency, end_of_month })
+    }
+
+    fn roll(&self, anchor: NaiveDate, months: i32) -> NaiveDate {
+        let rolled: NaiveDate = if months >= 0 {
+            anchor + Months::new(months as u32)
+        } else {
+            anchor - Months::new((-months) as u32)
+        };
+        if self.end_of_month && anchor == get_eom(anchor) {
+            return get_eom(rolled);
+        }
+        rolled
+    }
+}
+// time_fraction and time_fraction_with_reference panic where try_time_fraction and try_time_fraction_with_reference fail.
+impl TimeFractionCalc for ActualActualICMA {
+    fn time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
+        self.try_time_fraction(start_date, end_date).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn time_fraction_with_reference(&self, start_date: NaiveDate, end_date: NaiveDate, reference_start: NaiveDate, reference_end: NaiveDate) -> f64 {
+        self.try_time_fraction_with_reference(start_date, end_date, reference_start, reference_end).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_time_fraction(&self, start_date: NaiveDate, end_date: NaiveDate) -> Result<f64, DayCountError> {
+        match self.frequency {
+            Some(frequency) => {
+                let reference_start: NaiveDate = self.roll(end_date, -(12 / frequency as i32));
+                self.try_time_fraction_with_reference(start_date, end_date, reference_start, end_date)
+            },
+            None => Err(DayCountError::MissingReferencePeriod),
+        }
+    }
+
+    fn try_time_fraction_with_reference(&self, start_date: NaiveDate, end_date: NaiveDate, reference_start: NaiveDate, reference_end: NaiveDate) -> Result<f64, DayCountError> {
+        if end_date < start_date {
+            return Ok(-self.try_time_fraction_with_reference(end_date, start_date, reference_start, reference_end)?);
+        }
+        let reference_months: i32 = (reference_end.year() - reference_start.year()) * 12 + reference_end.month() as i32 - reference_start.month() as i32;
+        let months: i32 = match self.frequency {
+            Some(frequency) => 12 / frequency as i32,
+            None => {
+                if reference_months <= 0 || 12 % reference_months != 0 {
+                    return Err(DayCountError::InvalidReferencePeriod { reference_start, reference_end });
+                }
+                reference_months
+            },
+        };
+        let frequency: f64 = 12.0 / months as f64;
+
+        // Quasi coupon date k: k <= 0 rolls back from reference_start, k >= 1 rolls forward from reference_end.
+        let quasi_date = |k: i32| -> NaiveDate {
+            if k <= 0 { self.roll(reference_start, k * months) } else { self.roll(reference_end, (k - 1) * months) }
+        };
+        let mut k: i32 = 0;
+        while quasi_date(k) > start_date {
+            k -= 1;
+        }
+        while quasi_date(k + 1) <= start_date {
+            k += 1;
+        }
+
+        let mut year_fraction: f64 = 0.0;
+        while quasi_date(k) < end_date {
+            let (q_start, q_end) = (quasi_date(k), quasi_date(k + 1));
+            let overlap: i64 = (end_date.min(q_end) - start_date.max(q_start)).num_days();
+            year_fraction += overlap as f64 / ((q_end - q_start).num_days() as f64 * frequency);
+            k += 1;
+        }
+        Ok(year_fraction)
+    }
+}
+
+// Day count conventions by market name. Parsing is case insensitive, ignores spaces and parentheses, and accepts
+// "ACTUAL" for "ACT" ("Actual/Actual (ISDA)" == "ACT/ACT ISDA"). Display gives the canonical name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DayCountConvention {
+    Actual360,
+    Actual365Fixed,
+    ActualActualISDA,
+    ActualActualICMA,
+    ActualActualAFB,
+    Actual364,
+    Actual365L,
+    NoLeap365,
+    Thirty360BondBasis,
+    Thirty360US,
+    Thirty360E,
+    Thirty360EISDA,
+    Thirty360EPlus,
+    Thirty360German,
+    Business252,
+}
+impl DayCountConvention {
+    // Fails for conventions that need a calendar (BUS/252), use bind for those, or a coupon frequency (ACT/ACT ICMA),
+    // use with_frequency for those.
+    pub fn time_fraction_calc(&self) -> Result<Box<dyn TimeFractionCalc>, DayCountError> {
+        let calc: Box<dyn TimeFractionCalc> = match self {
+            DayCountConvention::Actual360 => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(ActualCounter), base: 360.0}),
+            DayCountConvention::Actual365Fixed => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(ActualCounter), base: 365.0}),
+            DayCountConvention::ActualActualISDA => Box::new(ActualActualISDA::new()),
+            DayCountConvention::ActualActualICMA => return Err(DayCountError::MissingFrequency { convention: *self }),
+            DayCountConvention::ActualActualAFB => Box::new(ActualActualAFB),
+            DayCountConvention::Actual364 => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(ActualCounter), base: 364.0}),
+            DayCountConvention::Actual365L => Box::new(Actual365L::new(false)),
+            DayCountConvention::NoLeap365 => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(NoLeapCounter), base: 365.0}),
+            DayCountConvention::Thirty360BondBasis => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30BondCounter)}), base: 360.0}),
+            DayCountConvention::Thirty360US => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30UCounter)}), base: 360.0}),
+            DayCountConvention::Thirty360E => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30ECounter)}), base: 360.0}),
+            DayCountConvention::Thirty360EISDA => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30EISDACounter::new(None))}), base: 360.0}),
+            DayCountConvention::Thirty360EPlus => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30EPlusCounter)}), base: 360.0}),
+            DayCountConvention::Thirty360German => Box::new(FixedBaseTimeFractionCalc{day_counter: Box::new(Days30Counter{backend: Box::new(Days30GermanCounter)}), base: 360.0}),
+            DayCountConvention::Business252 => return Err(DayCountError::MissingCalendar { convention: *self }),
+        };
+        Ok(calc)
+    }
+
+    pub fn bind<'a>(&self, calendar: &'a Calendar) -> Result<Box<dyn TimeFractionCalc + 'a>, DayCountError> {
+        match self {
+            DayCountConvention::Business252 => Ok(Box::new(business_252(calendar))),
+            _ => self.time_fraction_calc(),
+        }
+    }
+
+    // Coupon frequency is the number of coupons per year. Only ACT/ACT ICMA uses it, other conventions ignore it.
+    pub fn with_frequency(&self, frequency: u32) -> Result<Box<dyn TimeFractionCalc>, DayCountError> {
+        match self {
+            DayCountConvention::ActualActualICMA => Ok(Box::new(ActualActualICMA::new(Some(frequency), false)?)),
+            _ => self.time_fraction_calc(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDayCountConventionError {
+    pub input: String,
+}
+impl fmt::Display for ParseDayCountConventionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown day count convention '{}'.", self.input)
+    }
+}
+impl Error for ParseDayCountConventionError {}
+
+impl FromStr for DayCountConvention {
+    type Err = ParseDayCountConventionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key: String = s.to_uppercase().replace("ACTUAL", "ACT").chars().filter(|c| !matches!(c, ' ' | '(' | ')')).collect();
+        match &key[..] {
+            "ACT/360" | "A/360" | "A360" | "FRENCH" => Ok(DayCountConvention::Actual360),
+            "ACT/365F" | "ACT/365FIXED" | "ACT/365" | "A/365F" | "A365F" | "A365" | "ENGLISH" => Ok(DayCountConvention::Actual365Fixed),
+            "ACT/ACTISDA" | "ACT/ACT" | "ACT/365ISDA" | "ACT/ACTHISTORICAL" | "A/AISDA" => Ok(DayCountConvention::ActualActualISDA),
+            "ACT/ACTICMA" | "ACT/ACTISMA" | "ACT/ACTBOND" | "ISMA-99" | "A/AICMA" => Ok(DayCountConvention::ActualActualICMA),
+            "ACT/ACTAFB" | "ACT/ACTFRF" | "ACT/ACTEURO" | "A/AAFB" => Ok(DayCountConvention::ActualActualAFB),
+            "ACT/364" | "A/364" | "A364" => Ok(DayCountConvention::Actual364),
+            "ACT/365L" | "ACT/365LEAP" | "A/365L" | "ISMA-YEAR" => Ok(DayCountConvention::Actual365L),
+            "NL/365" | "ACT/365NL" | "ACT/365NOLEAP" | "NL365" => Ok(DayCountConvention::NoLeap365),
+            "30/360" | "30/360BONDBASIS" | "BONDBASIS" | "360/360" | "30A/360" => Ok(DayCountConvention::Thirty360BondBasis),
+            "30/360US" | "30U/360" | "30US/360" | "30/360SIA" => Ok(DayCountConvention::Thirty360US),
+            "30E/360" | "EUROBONDBASIS" | "30/360ICMA" | "30S/360" => Ok(DayCountConvention::Thirty360E),
+            "30E/360ISDA" => Ok(DayCountConvention::Thirty360EISDA),
+            "30E+/360" | "30E+/360ISDA" => Ok(DayCountConvention::Thirty360EPlus),
+            "30/360GERMAN" | "GERMAN" | "30G/360" => Ok(DayCountConvention::Thirty360German),
+            "BUS/252" | "BD/252" | "BUS252" => Ok(DayCountConvention::Business252),
+            _ => Err(ParseDayCountConventionError { input: s.to_string() }),
+        }
+    }
+}
+
+impl fmt::Display for DayCountConvention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: &str = match self {
+            DayCountConvention::Actual360 => "ACT/360",
+            DayCountConvention::Actual365Fixed => "ACT/365F",
+            DayCountConvention::ActualActualISDA => "ACT/ACT ISDA",
+            DayCountConvention::ActualActualICMA => "ACT/ACT ICMA",
+            DayCountConvention::ActualActualAFB => "ACT/ACT AFB",
+            DayCountConvention::Actual364 => "ACT/364",
+            DayCountConvention::Actual365L => "ACT/365L",
+            DayCountConvention::NoLeap365 => "NL/365",
+            DayCountConvention::Thirty360BondBasis => "30/360",
+            DayCountConvention::Thirty360US => "30U/360",
+            DayCountConvention::Thirty360E => "30E/360",
+            DayCountConvention::Thirty360EISDA => "30E/360 ISDA",
+            DayCountConvention::Thirty360EPlus => "30E+/360",
+            DayCountConvention::Thirty360German => "30/360 German",
+            DayCountConvention::Business252 => "BUS/252",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,33 @@
+pub mod dates;
+
+pub use dates::{aux_funcs, calendar_registry, calendars, date_adjusting, day_counting, imm, scheduling, settlement, tenors, time_fractions};
+
+pub mod prelude {
+    pub use crate::dates::calendar_registry::{get_calendar, get_registered_calendar_codes, is_registered_calendar, register_calendar, InvalidCalendarCodeError, UnknownCalendarError};
+    pub use crate::dates::calendars::{
+        easter_sunday, get_hong_kong_calendar, get_london_calendar, get_mexico_city_calendar, get_ny_calendar, get_riyadh_calendar,
+        get_santiago_calendar, get_sao_paulo_calendar, get_shanghai_calendar, get_sifma_calendar, get_singapore_calendar,
+        get_sydney_calendar, get_target_calendar, get_tokyo_calendar, get_toronto_calendar, get_zurich_calendar, BridgeDayRule, Calendar,
+        Computus, DaySpanRule, EasterOffsetRule, HolidayRule, LastWeekWeekdayRule, LunarCalendar, LunarDateRule, MonthDayRule, Observance,
+        ObservedRule, OnWeekdaysRule, OrdinalWeekWeekdayRule, Season, SeasonRule, SolarTermRule, SubstituteDayRule, TabulatedRule,
+        WeekdayShiftRule, YearRangeRule,
+    };
+    pub use crate::dates::date_adjusting::{
+        BusinessDayConvention, DateAdjustingMethod, FloatingRateNote, Following, HalfMonthModifiedFollowing, ModifiedFollowing, ModifiedPreceding,
+        Nearest, Preceding, Unadjusted,
+    };
+    pub use crate::dates::day_counting::{
+        ActualCounter, BusinessDayCounter, DayCounter, Days30Backend, Days30BondCounter, Days30Counter, Days30ECounter,
+        Days30EISDACounter, Days30EPlusCounter, Days30GermanCounter, Days30UCounter, NoLeapCounter,
+    };
+    pub use crate::dates::imm::{
+        cds_maturity_date, cds_roll_date, imm_code, imm_date, imm_date_from_code, is_cds_date, is_imm_date, next_cds_date, next_imm_date,
+        previous_cds_date, previous_imm_date, ParseImmCodeError,
+    };
+    pub use crate::dates::scheduling::{AccrualPeriod, CouponFrequency, Maturity, RollType, ScheduleDefinition, ScheduleError, SchedulePeriod, StubType};
+    pub use crate::dates::settlement::{spot_date, SettlementLags, SettlementRule};
+    pub use crate::dates::tenors::{MoneyMarketCode, ParseTenorError, Tenor, TenorDateError};
+    pub use crate::dates::time_fractions::{
+        business_252, Actual365L, ActualActualAFB, ActualActualICMA, ActualActualISDA, DayCountConvention, DayCountError, FixedBaseTimeFractionCalc, TimeFractionCalc,
+    };
+}
@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+use std::sync::Arc;
+use std::thread;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn built_in_codes() {
+    for code in ["USNY", "USGS", "GBLO", "EUTA", "JPTO", "CHZU", "CATO", "AUSY", "BRSP", "CLSA", "MXMC", "HKHK", "SGSI", "CNBE", "CNSH", "SARI"] {
+        assert!(is_registered_calendar(code), "{}", code);
+        assert!(get_registered_calendar_codes().contains(&code.to_string()));
+    }
+    let usny = get_calendar("USNY").unwrap();
+    assert!(!usny.is_business_day(ymd(2024, 7, 4)));
+    assert!(Arc::ptr_eq(&usny, &get_calendar(" usny ").unwrap()));
+    assert!(!get_calendar("GBLO").unwrap().is_business_day(ymd(2024, 8, 26)));
+    assert!(!get_calendar("EUTA").unwrap().is_business_day(ymd(2024, 4, 1)));
+    // Shanghai is registered under both codes.
+    assert!(!get_calendar("CNSH").unwrap().is_business_day(ymd(2024, 10, 1)));
+    assert!(!get_calendar("CNBE").unwrap().is_business_day(ymd(2024, 10, 1)));
+}
+
+#[test]
+fn combined_codes_are_cached() {
+    let combined = get_calendar("USNY+GBLO").unwrap();
+    assert!(!combined.is_business_day(ymd(2024, 7, 4)));
+    assert!(!combined.is_business_day(ymd(2024, 8, 26)));
+    assert!(combined.is_business_day(ymd(2024, 8, 27)));
+    assert!(Arc::ptr_eq(&combined, &get_calendar("gblo+USNY").unwrap()));
+    assert!(Arc::ptr_eq(&combined, &get_calendar("USNY + GBLO + USNY").unwrap()));
+    assert!(Arc::ptr_eq(&get_calendar("USNY+USNY").unwrap(), &get_calendar("USNY").unwrap()));
+}
+
+#[test]
+fn unknown_codes() {
+    assert_eq!(get_calendar("XXXX").err(), Some(UnknownCalendarError { code: "XXXX".to_string() }));
+    assert_eq!(get_calendar("USNY+xxxx").err(), Some(UnknownCalendarError { code: "XXXX".to_string() }));
+    assert_eq!(get_calendar("USNY+").err(), Some(UnknownCalendarError { code: "USNY+".to_string() }));
+    assert!(!is_registered_calendar("XXXX"));
+}
+
+#[test]
+fn custom_calendars() {
+    let holiday: NaiveDate = ymd(2024, 3, 12);
+    register_calendar("test1", Calendar::new(None, Some(vec![holiday]))).unwrap();
+    assert!(is_registered_calendar("TEST1"));
+    assert!(!get_calendar("TEST1").unwrap().is_business_day(holiday));
+    let combined = get_calendar("TEST1+EUTA").unwrap();
+    assert!(!combined.is_business_day(holiday));
+    assert!(!combined.is_business_day(ymd(2024, 12, 26)));
+
+    // Replacing a calendar drops the cached combinations that include it.
+    register_calendar("TEST1", Calendar::new(None, None)).unwrap();
+    assert!(get_calendar("TEST1").unwrap().is_business_day(holiday));
+    let recombined = get_calendar("EUTA+TEST1").unwrap();
+    assert!(!Arc::ptr_eq(&combined, &recombined));
+    assert!(recombined.is_business_day(holiday));
+    // Calendars already handed out keep their holidays.
+    assert!(!combined.is_business_day(holiday));
+}
+
+#[test]
+fn invalid_custom_codes() {
+    assert_eq!(register_calendar("TEST2+USNY", Calendar::new(None, None)), Err(InvalidCalendarCodeError { code: "TEST2+USNY".to_string() }));
+    assert_eq!(register_calendar(" ", Calendar::new(None, None)), Err(InvalidCalendarCodeError { code: " ".to_string() }));
+    assert!(!is_registered_calendar("TEST2"));
+    assert!(!is_registered_calendar("TEST2+USNY"));
+}
+
+#[test]
+fn shared_between_threads() {
+    let handles: Vec<thread::JoinHandle<Arc<Calendar>>> = (0..8)
+        .map(|_| thread::spawn(|| {
+            let calendar = get_calendar("JPTO+CHZU+CATO").unwrap();
+            calendar.add_business_days(ymd(2024, 12, 24), 3);
+            calendar
+        }))
+        .collect();
+    let calendars: Vec<Arc<Calendar>> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert!(calendars.iter().all(|calendar| Arc::ptr_eq(calendar, &calendars[0])));
+    // 25th, 26th, 31st of December and 1st to 3rd of January are closed in one of the centres.
+    assert_eq!(calendars[0].add_business_days(ymd(2024, 12, 24), 3), ymd(2025, 1, 6));
+}
@@ -0,0 +1,586 @@
+use chrono::{Datelike, Month, NaiveDate, Weekday};
+use dates::prelude::*;
+
+type Ymd = (i32, u32, u32);
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+// Holidays falling on weekdays, the ones published lists and business day arithmetic care about.
+fn weekday_holidays(calendar: &Calendar, year: i32) -> Vec<NaiveDate> {
+    ymd(year, 1, 1).iter_days().take_while(|date| date.year() == year)
+        .filter(|date| !calendar.is_weekend(*date) && !calendar.is_business_day(*date))
+        .collect()
+}
+
+fn check_holidays(calendar: &Calendar, year: i32, expected: &[(u32, u32)]) {
+    let expected: Vec<NaiveDate> = expected.iter().map(|(m, d)| ymd(year, *m, *d)).collect();
+    assert_eq!(weekday_holidays(calendar, year), expected, "holidays of {}", year);
+}
+
+#[test]
+fn london() {
+    let calendar = get_london_calendar(None, None, None);
+    check_holidays(&calendar, 2022, &[(1, 3), (4, 15), (4, 18), (5, 2), (6, 2), (6, 3), (8, 29), (9, 19), (12, 26), (12, 27)]);
+    check_holidays(&calendar, 2023, &[(1, 2), (4, 7), (4, 10), (5, 1), (5, 8), (5, 29), (8, 28), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (3, 29), (4, 1), (5, 6), (5, 27), (8, 26), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (4, 18), (4, 21), (5, 5), (5, 26), (8, 25), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2021, &[(1, 1), (4, 2), (4, 5), (5, 3), (5, 31), (8, 30), (12, 27), (12, 28)]);
+}
+
+#[test]
+fn target() {
+    let calendar = get_target_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(4, 7), (4, 10), (5, 1), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (3, 29), (4, 1), (5, 1), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (4, 18), (4, 21), (5, 1), (12, 25), (12, 26)]);
+}
+
+#[test]
+fn tokyo() {
+    let calendar = get_tokyo_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (1, 3), (1, 9), (2, 23), (3, 21), (5, 3), (5, 4), (5, 5), (7, 17), (8, 11), (9, 18), (10, 9), (11, 3), (11, 23)]);
+    check_holidays(&calendar, 2024, &[
+        (1, 1), (1, 2), (1, 3), (1, 8), (2, 12), (2, 23), (3, 20), (4, 29), (5, 3), (5, 6), (7, 15), (8, 12), (9, 16), (9, 23), (10, 14),
+        (11, 4), (12, 31),
+    ]);
+    check_holidays(&calendar, 2025, &[
+        (1, 1), (1, 2), (1, 3), (1, 13), (2, 11), (2, 24), (3, 20), (4, 29), (5, 5), (5, 6), (7, 21), (8, 11), (9, 15), (9, 23), (10, 13),
+        (11, 3), (11, 24), (12, 31),
+    ]);
+    // Olympic Games moves.
+    check_holidays(&calendar, 2021, &[
+        (1, 1), (1, 11), (2, 11), (2, 23), (4, 29), (5, 3), (5, 4), (5, 5), (7, 22), (7, 23), (8, 9), (9, 20), (9, 23), (11, 3), (11, 23),
+        (12, 31),
+    ]);
+    // Citizens' holiday between Respect for the Aged Day and the autumnal equinox.
+    assert!(!calendar.is_business_day(ymd(2026, 9, 22)));
+    assert!(calendar.is_business_day(ymd(2025, 9, 16)));
+}
+
+#[test]
+fn zurich() {
+    let calendar = get_zurich_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (4, 7), (4, 10), (5, 1), (5, 18), (5, 29), (8, 1), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (1, 2), (3, 29), (4, 1), (5, 1), (5, 9), (5, 20), (8, 1), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 2), (4, 18), (4, 21), (5, 1), (5, 29), (6, 9), (8, 1), (12, 25), (12, 26)]);
+}
+
+#[test]
+fn toronto() {
+    let calendar = get_toronto_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (2, 20), (4, 7), (5, 22), (7, 3), (8, 7), (9, 4), (10, 2), (10, 9), (11, 13), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 19), (3, 29), (5, 20), (7, 1), (8, 5), (9, 2), (9, 30), (10, 14), (11, 11), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (2, 17), (4, 18), (5, 19), (7, 1), (8, 4), (9, 1), (9, 30), (10, 13), (11, 11), (12, 25), (12, 26)]);
+}
+
+#[test]
+fn sydney() {
+    let calendar = get_sydney_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (1, 26), (4, 7), (4, 10), (4, 25), (6, 12), (8, 7), (10, 2), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (1, 26), (3, 29), (4, 1), (4, 25), (6, 10), (8, 5), (10, 7), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 27), (4, 18), (4, 21), (4, 25), (6, 9), (8, 4), (10, 6), (12, 25), (12, 26)]);
+}
+
+#[test]
+fn sao_paulo() {
+    let calendar = get_sao_paulo_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(2, 20), (2, 21), (4, 7), (4, 21), (5, 1), (6, 8), (9, 7), (10, 12), (11, 2), (11, 15), (12, 25), (12, 29)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 12), (2, 13), (3, 29), (5, 1), (5, 30), (11, 15), (11, 20), (12, 24), (12, 25), (12, 31)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (3, 3), (3, 4), (4, 18), (4, 21), (5, 1), (6, 19), (11, 20), (12, 24), (12, 25), (12, 31)]);
+}
+
+#[test]
+fn santiago() {
+    let calendar = get_santiago_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (4, 7), (5, 1), (6, 21), (6, 26), (8, 15), (9, 18), (9, 19), (10, 9), (10, 27), (11, 1), (12, 8), (12, 25)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (3, 29), (5, 1), (5, 21), (6, 20), (7, 16), (8, 15), (9, 18), (9, 19), (9, 20), (10, 31), (11, 1), (12, 25), (12, 31)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (4, 18), (5, 1), (5, 21), (6, 20), (7, 16), (8, 15), (9, 18), (9, 19), (10, 31), (12, 8), (12, 25), (12, 31)]);
+}
+
+#[test]
+fn mexico_city() {
+    let calendar = get_mexico_city_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(2, 6), (3, 20), (4, 6), (4, 7), (5, 1), (11, 2), (11, 20), (12, 12), (12, 25)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 5), (3, 18), (3, 28), (3, 29), (5, 1), (9, 16), (10, 1), (11, 18), (12, 12), (12, 25)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (2, 3), (3, 17), (4, 17), (4, 18), (5, 1), (9, 16), (11, 17), (12, 12), (12, 25)]);
+}
+
+#[test]
+fn sifma() {
+    let calendar = get_sifma_calendar(None, None, None);
+    check_holidays(&calendar, 2024, &[(1, 1), (1, 15), (2, 19), (3, 29), (5, 27), (6, 19), (7, 4), (9, 2), (10, 14), (11, 11), (11, 28), (12, 25)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 20), (2, 17), (4, 18), (5, 26), (6, 19), (7, 4), (9, 1), (10, 13), (11, 11), (11, 27), (12, 25)]);
+    assert!(calendar.is_business_day(ymd(2023, 4, 7))); // Good Friday early close
+    assert!(calendar.is_business_day(ymd(2021, 6, 18))); // Before Juneteenth was observed
+    assert!(!calendar.is_business_day(ymd(2022, 6, 20)));
+    assert!(!calendar.is_business_day(ymd(2012, 10, 30)));
+}
+
+#[test]
+fn built_in_calendars_with_year_range() {
+    let specials: [Ymd; 2] = [(2024, 7, 10), (2025, 3, 12)];
+    let holidays: Vec<NaiveDate> = specials.iter().map(|(y, m, d)| ymd(*y, *m, *d)).collect();
+    let calendar = get_london_calendar(Some(holidays), Some(2020), Some(2025));
+    assert!(calendar.is_explicit_holiday(ymd(2024, 7, 10)));
+    assert!(calendar.is_explicit_holiday(ymd(2024, 5, 27)));
+    assert!(calendar.is_explicit_holiday(ymd(2022, 6, 3)));
+    // Cancelled rule holidays stay cancelled when rules are expanded into explicit holidays.
+    assert!(!calendar.is_holiday(ymd(2022, 5, 30)));
+    assert!(calendar.is_removed_holiday(ymd(2022, 5, 30)));
+}
+
+#[test]
+fn season_and_moving_rules() {
+    assert_eq!(SeasonRule::new(Season::MarchEquinox, 0).get_date(2024), Some(ymd(2024, 3, 20)));
+    assert_eq!(SeasonRule::new(Season::JuneSolstice, 0).get_date(2024), Some(ymd(2024, 6, 20)));
+    assert_eq!(SeasonRule::new(Season::SeptemberEquinox, 0).get_date(2024), Some(ymd(2024, 9, 22)));
+    assert_eq!(SeasonRule::new(Season::DecemberSolstice, 0).get_date(2024), Some(ymd(2024, 12, 21)));
+    // 2025 June solstice is at 02:42 UTC.
+    assert_eq!(SeasonRule::new(Season::JuneSolstice, 0).get_date(2025), Some(ymd(2025, 6, 21)));
+    assert_eq!(SeasonRule::new(Season::JuneSolstice, -4).get_date(2025), Some(ymd(2025, 6, 20)));
+
+    let to_monday = WeekdayShiftRule::new(Box::new(MonthDayRule::new(Month::June, 29)), [0, -1, -2, -3, 3, 0, 0]);
+    assert_eq!(to_monday.get_date(2023), Some(ymd(2023, 6, 26))); // Thursday
+    assert_eq!(to_monday.get_date(2022), Some(ymd(2022, 6, 27))); // Wednesday
+    assert_eq!(to_monday.get_date(2018), Some(ymd(2018, 7, 2))); // Friday
+    assert_eq!(to_monday.get_date(2024), Some(ymd(2024, 6, 29))); // Saturday
+
+    let bridge = OnWeekdaysRule::new(Box::new(MonthDayRule::new(Month::September, 20)), &[Weekday::Fri]);
+    assert_eq!(bridge.get_dates(2024), vec![ymd(2024, 9, 20)]);
+    assert_eq!(bridge.get_dates(2025), vec![]);
+    let ranged = YearRangeRule::new(Box::new(EasterOffsetRule::new(39)), Some(2020), Some(2024));
+    assert_eq!(ranged.get_dates(2024), vec![ymd(2024, 5, 9)]);
+    assert_eq!(ranged.get_dates(2025), vec![]);
+    assert_eq!(ranged.get_dates(2019), vec![]);
+}
+
+#[test]
+fn intersection_and_difference() {
+    let ny = get_ny_calendar(None, None, None);
+    let london = get_london_calendar(None, None, None);
+
+    // Open if either centre is open.
+    let either_open = ny.intersect(london.clone());
+    assert!(either_open.is_business_day(ymd(2024, 7, 4)));
+    assert!(either_open.is_business_day(ymd(2024, 8, 26)));
+    assert!(either_open.is_business_day(ymd(2024, 12, 26)));
+    assert!(!either_open.is_business_day(ymd(2024, 12, 25)));
+    assert!(either_open.is_holiday(ymd(2024, 5, 27)));
+    assert!(!either_open.is_holiday(ymd(2024, 7, 6)));
+    assert_eq!(either_open.add_business_days(ymd(2024, 12, 24), 1), ymd(2024, 12, 26));
+    assert_eq!(either_open.add_business_days(ymd(2024, 7, 3), 1), ymd(2024, 7, 4));
+    assert_eq!(either_open.substract_business_days(ymd(2024, 12, 27), 2), ymd(2024, 12, 24));
+    assert_eq!(either_open.business_days_between(ymd(2024, 12, 23), ymd(2025, 1, 6)), 8);
+
+    // New York holidays on which London is open.
+    let ny_only = ny.clone() - london.clone();
+    assert_eq!(ny_only.get_weekend(), vec![Weekday::Sat, Weekday::Sun]);
+    assert!(ny_only.is_holiday(ymd(2024, 7, 4)));
+    assert!(!ny_only.is_holiday(ymd(2024, 8, 26)));
+    // Christmas is closed in both centres, so it is a business day of the difference.
+    assert!(!ny_only.is_holiday(ymd(2024, 12, 25)));
+    assert!(ny_only.is_business_day(ymd(2024, 12, 25)));
+    assert!(!ny_only.is_business_day(ymd(2024, 7, 6)));
+    assert_eq!(ny_only.add_business_days(ymd(2024, 12, 24), 1), ymd(2024, 12, 25));
+
+    // Compositions can be combined again and keep their own explicit holidays.
+    let mut nested = (ny & london) + Calendar::new(None, Some(vec![ymd(2024, 7, 4)]));
+    assert!(!nested.is_business_day(ymd(2024, 7, 4)));
+    assert!(!nested.is_business_day(ymd(2024, 12, 25)));
+    assert!(nested.is_business_day(ymd(2024, 8, 26)));
+    nested.remove_holiday(ymd(2024, 12, 25));
+    assert!(nested.is_business_day(ymd(2024, 12, 25)));
+    nested.add_holiday(ymd(2024, 8, 26));
+    assert_eq!(nested.add_business_days(ymd(2024, 8, 23), 1), ymd(2024, 8, 27));
+}
+
+#[test]
+fn compositions_with_different_weekends() {
+    let friday_saturday = Calendar::new_with_weekend(None, Some(vec![ymd(2024, 4, 10)]), &[Weekday::Fri, Weekday::Sat]);
+    let saturday_sunday = Calendar::new(None, Some(vec![ymd(2024, 4, 10), ymd(2024, 4, 11)]));
+
+    let either_open = friday_saturday.intersect(saturday_sunday.clone());
+    assert_eq!(either_open.get_weekend(), vec![Weekday::Sat]);
+    assert!(either_open.is_business_day(ymd(2024, 4, 12)));
+    assert!(either_open.is_business_day(ymd(2024, 4, 14)));
+    assert!(either_open.is_business_day(ymd(2024, 4, 11)));
+    assert!(either_open.is_holiday(ymd(2024, 4, 10)));
+    assert_eq!(either_open.add_business_days(ymd(2024, 4, 9), 1), ymd(2024, 4, 11));
+
+    let both_open = friday_saturday.clone() + saturday_sunday.clone();
+    assert_eq!(both_open.get_weekend(), vec![Weekday::Fri, Weekday::Sat, Weekday::Sun]);
+    assert_eq!(both_open.add_business_days(ymd(2024, 4, 9), 1), ymd(2024, 4, 15));
+
+    // Saturday-Sunday holidays on which the Friday-Saturday centre is open, Sundays are still weekend.
+    let difference = saturday_sunday - friday_saturday;
+    assert_eq!(difference.get_weekend(), vec![Weekday::Sat, Weekday::Sun]);
+    assert!(difference.is_business_day(ymd(2024, 4, 10)));
+    assert!(difference.is_holiday(ymd(2024, 4, 11)));
+    assert!(difference.is_business_day(ymd(2024, 4, 12)));
+    assert!(!difference.is_business_day(ymd(2024, 4, 14)));
+}
+
+#[test]
+fn easter_rules() {
+    assert_eq!(easter_sunday(2024, Computus::Western), ymd(2024, 3, 31));
+    assert_eq!(easter_sunday(2026, Computus::Western), ymd(2026, 4, 5));
+    assert_eq!(easter_sunday(2021, Computus::Orthodox), ymd(2021, 5, 2));
+    assert_eq!(easter_sunday(2023, Computus::Orthodox), ymd(2023, 4, 16));
+    assert_eq!(easter_sunday(2024, Computus::Orthodox), ymd(2024, 5, 5));
+    assert_eq!(easter_sunday(2025, Computus::Orthodox), ymd(2025, 4, 20));
+    assert_eq!(easter_sunday(2026, Computus::Orthodox), ymd(2026, 4, 12));
+
+    assert_eq!(EasterOffsetRule::new(-3).get_date(2024), Some(ymd(2024, 3, 28))); // Maundy Thursday
+    assert_eq!(EasterOffsetRule::new(-2).get_date(2024), Some(ymd(2024, 3, 29))); // Good Friday
+    assert_eq!(EasterOffsetRule::new(1).get_date(2024), Some(ymd(2024, 4, 1))); // Easter Monday
+    assert_eq!(EasterOffsetRule::new(-48).get_date(2025), Some(ymd(2025, 3, 3))); // Carnival Monday
+    assert_eq!(EasterOffsetRule::new(39).get_date(2025), Some(ymd(2025, 5, 29))); // Ascension Day
+    assert_eq!(EasterOffsetRule::new(50).get_date(2025), Some(ymd(2025, 6, 9))); // Whit Monday
+    assert_eq!(EasterOffsetRule::new(60).get_date(2025), Some(ymd(2025, 6, 19))); // Corpus Christi
+    // Greek Clean Monday, Orthodox Good Friday and Orthodox Whit Monday.
+    assert_eq!(EasterOffsetRule::new_with_computus(-48, Computus::Orthodox).get_date(2024), Some(ymd(2024, 3, 18)));
+    assert_eq!(EasterOffsetRule::new_with_computus(-2, Computus::Orthodox).get_date(2024), Some(ymd(2024, 5, 3)));
+    assert_eq!(EasterOffsetRule::new_with_computus(50, Computus::Orthodox).get_date(2024), Some(ymd(2024, 6, 24)));
+}
+
+#[test]
+fn lunar_rules() {
+    let new_year = LunarDateRule::new(LunarCalendar::Chinese, 1, 1);
+    assert_eq!(new_year.get_date(2024), Some(ymd(2024, 2, 10)));
+    assert_eq!(new_year.get_date(2025), Some(ymd(2025, 1, 29)));
+    // 2023 has a leap second month, later months are one month later than without it.
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 3, 1).get_date(2023), Some(ymd(2023, 4, 20)));
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 8, 15).get_date(2024), Some(ymd(2024, 9, 17)));
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 8, 15).get_date(2025), Some(ymd(2025, 10, 6)));
+    // Twelfth month dates of the previous lunar year fall in January, 30th days only exist in long months.
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 12, 8).get_dates(2025), vec![ymd(2025, 1, 7)]);
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 12, 30).get_dates(2024), vec![ymd(2024, 2, 9)]);
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 12, 30).get_dates(2025), vec![]);
+    assert_eq!(new_year.get_dates(1900), vec![]);
+
+    let eid_al_fitr = LunarDateRule::new(LunarCalendar::Hijri, 10, 1);
+    assert_eq!(eid_al_fitr.get_date(2024), Some(ymd(2024, 4, 10)));
+    assert_eq!(eid_al_fitr.get_date(2025), Some(ymd(2025, 3, 30)));
+    assert_eq!(LunarDateRule::new(LunarCalendar::Hijri, 12, 10).get_date(2024), Some(ymd(2024, 6, 16)));
+    // Hijri years are shorter, 1 Muharram fell twice in 2008.
+    assert_eq!(LunarDateRule::new(LunarCalendar::Hijri, 1, 1).get_dates(2008), vec![ymd(2008, 1, 10), ymd(2008, 12, 29)]);
+
+    let spring_festival = DaySpanRule::new(Box::new(new_year.clone()), -1, 3);
+    assert_eq!(spring_festival.get_date(2024), Some(ymd(2024, 2, 9)));
+    assert_eq!(spring_festival.get_dates(2024), vec![ymd(2024, 2, 9), ymd(2024, 2, 10), ymd(2024, 2, 11)]);
+    let year_end = DaySpanRule::new(Box::new(MonthDayRule::new(Month::December, 31)), -1, 3);
+    assert_eq!(year_end.get_dates(2024), vec![ymd(2024, 1, 1), ymd(2024, 12, 30), ymd(2024, 12, 31)]);
+
+    // Sunday days of a span move after the last day.
+    let span = DaySpanRule::new(Box::new(new_year), 0, 3);
+    let substituted = SubstituteDayRule::new_with_weekend(Box::new(span), vec![], &[Weekday::Sun]);
+    assert_eq!(substituted.get_dates(2023), vec![ymd(2023, 1, 23), ymd(2023, 1, 24), ymd(2023, 1, 25)]);
+    assert_eq!(substituted.get_dates(2024), vec![ymd(2024, 2, 10), ymd(2024, 2, 12), ymd(2024, 2, 13)]);
+
+    assert_eq!(SolarTermRule::new(15, 8).get_date(2024), Some(ymd(2024, 4, 4)));
+    assert_eq!(SolarTermRule::new(15, 8).get_date(2026), Some(ymd(2026, 4, 5)));
+    assert_eq!(SolarTermRule::new(315, 8).get_date(2025), Some(ymd(2025, 2, 3)));
+    assert_eq!(SolarTermRule::new(270, 0).get_date(2024), SeasonRule::new(Season::DecemberSolstice, 0).get_date(2024));
+
+    let tabulated = TabulatedRule::new(vec![ymd(2024, 10, 31), ymd(2025, 10, 20)]);
+    assert_eq!(tabulated.get_dates(2025), vec![ymd(2025, 10, 20)]);
+    assert_eq!(tabulated.get_dates(2026), vec![]);
+}
+
+#[test]
+fn hong_kong() {
+    let calendar = get_hong_kong_calendar(None, None, None);
+    check_holidays(&calendar, 2021, &[(1, 1), (2, 12), (2, 15), (4, 2), (4, 5), (4, 6), (5, 19), (6, 14), (7, 1), (9, 22), (10, 1), (10, 14), (12, 27)]);
+    check_holidays(&calendar, 2022, &[(2, 1), (2, 2), (2, 3), (4, 5), (4, 15), (4, 18), (5, 2), (5, 9), (6, 3), (7, 1), (9, 12), (10, 4), (12, 26), (12, 27)]);
+    check_holidays(&calendar, 2023, &[(1, 2), (1, 23), (1, 24), (1, 25), (4, 5), (4, 7), (4, 10), (5, 1), (5, 26), (6, 22), (10, 2), (10, 23), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 12), (2, 13), (3, 29), (4, 1), (4, 4), (5, 1), (5, 15), (6, 10), (7, 1), (9, 18), (10, 1), (10, 11), (12, 25), (12, 26)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 29), (1, 30), (1, 31), (4, 4), (4, 18), (4, 21), (5, 1), (5, 5), (7, 1), (10, 1), (10, 7), (10, 29), (12, 25), (12, 26)]);
+}
+
+#[test]
+fn singapore() {
+    let calendar = get_singapore_calendar(None, None, None);
+    check_holidays(&calendar, 2023, &[(1, 2), (1, 23), (1, 24), (4, 7), (5, 1), (6, 2), (6, 29), (8, 9), (9, 1), (11, 13), (12, 25)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 12), (3, 29), (4, 10), (5, 1), (5, 22), (6, 17), (8, 9), (10, 31), (12, 25)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 29), (1, 30), (3, 31), (4, 18), (5, 1), (5, 12), (10, 20), (12, 25)]);
+    check_holidays(&calendar, 2026, &[(1, 1), (2, 17), (2, 18), (4, 3), (5, 1), (5, 27), (6, 1), (8, 10), (11, 9), (12, 25)]);
+}
+
+#[test]
+fn shanghai() {
+    let calendar = get_shanghai_calendar(None, None, None);
+    check_holidays(&calendar, 2020, &[(1, 1), (1, 24), (1, 27), (1, 28), (1, 29), (1, 30), (1, 31), (4, 6), (5, 1), (5, 4), (5, 5), (6, 25), (6, 26),
+                                      (10, 1), (10, 2), (10, 5), (10, 6), (10, 7), (10, 8)]);
+    check_holidays(&calendar, 2021, &[(1, 1), (2, 11), (2, 12), (2, 15), (2, 16), (2, 17), (4, 5), (5, 3), (5, 4), (5, 5), (6, 14), (9, 20), (9, 21),
+                                      (10, 1), (10, 4), (10, 5), (10, 6), (10, 7)]);
+    check_holidays(&calendar, 2022, &[(1, 3), (1, 31), (2, 1), (2, 2), (2, 3), (2, 4), (4, 4), (4, 5), (5, 2), (5, 3), (5, 4), (6, 3), (9, 12),
+                                      (10, 3), (10, 4), (10, 5), (10, 6), (10, 7)]);
+    check_holidays(&calendar, 2023, &[(1, 2), (1, 23), (1, 24), (1, 25), (1, 26), (1, 27), (4, 5), (5, 1), (5, 2), (5, 3), (6, 22), (6, 23), (9, 29),
+                                      (10, 2), (10, 3), (10, 4), (10, 5), (10, 6)]);
+    check_holidays(&calendar, 2024, &[(1, 1), (2, 9), (2, 12), (2, 13), (2, 14), (2, 15), (2, 16), (4, 4), (4, 5), (5, 1), (5, 2), (5, 3), (6, 10),
+                                      (9, 16), (9, 17), (10, 1), (10, 2), (10, 3), (10, 4), (10, 7)]);
+    check_holidays(&calendar, 2025, &[(1, 1), (1, 28), (1, 29), (1, 30), (1, 31), (2, 3), (2, 4), (4, 4), (5, 1), (5, 2), (5, 5), (6, 2),
+                                      (10, 1), (10, 2), (10, 3), (10, 6), (10, 7), (10, 8)]);
+}
+
+#[test]
+fn riyadh() {
+    let calendar = get_riyadh_calendar(None, None, None);
+    assert_eq!(calendar.get_weekend(), vec![Weekday::Fri, Weekday::Sat]);
+    assert!(calendar.is_business_day(ymd(2024, 4, 7)));
+    check_holidays(&calendar, 2024, &[(2, 22), (4, 9), (4, 10), (4, 11), (6, 16), (6, 17), (6, 18), (9, 23)]);
+    // Founding Day on a Saturday moves to Sunday, Eid al-Adha from Thursday 5 June.
+    check_holidays(&calendar, 2025, &[(2, 23), (3, 30), (3, 31), (4, 1), (4, 2), (6, 5), (6, 8), (9, 23)]);
+    assert_eq!(calendar.add_business_days(ymd(2024, 4, 8), 1), ymd(2024, 4, 14));
+}
+
+#[test]
+fn observance_rules() {
+    // 2026-07-04 is a Saturday, 2027-07-04 a Sunday.
+    let saturday: NaiveDate = ymd(2026, 7, 4);
+    let sunday: NaiveDate = ymd(2027, 7, 4);
+    assert_eq!(Observance::NearestWeekday.apply(saturday), ymd(2026, 7, 3));
+    assert_eq!(Observance::NearestWeekday.apply(sunday), ymd(2027, 7, 5));
+    assert_eq!(Observance::NextMonday.apply(saturday), ymd(2026, 7, 6));
+    assert_eq!(Observance::NextMonday.apply(sunday), ymd(2027, 7, 5));
+    assert_eq!(Observance::SundayToMonday.apply(saturday), saturday);
+    assert_eq!(Observance::SundayToMonday.apply(sunday), ymd(2027, 7, 5));
+    assert_eq!(Observance::SaturdayToFriday.apply(saturday), ymd(2026, 7, 3));
+    assert_eq!(Observance::SaturdayToFriday.apply(sunday), sunday);
+    assert_eq!(Observance::NearestWeekday.apply(ymd(2025, 7, 4)), ymd(2025, 7, 4));
+
+    let independence_day = ObservedRule::new(Box::new(MonthDayRule::new(Month::July, 4)), Observance::NearestWeekday);
+    assert_eq!(independence_day.get_date(2026), Some(ymd(2026, 7, 3)));
+    assert_eq!(independence_day.get_dates(2027), vec![ymd(2027, 7, 5)]);
+    let ny = get_ny_calendar(None, None, None);
+    assert!(!ny.is_business_day(ymd(2026, 7, 3)));
+    assert_eq!(ny.add_business_days(ymd(2026, 7, 2), 1), ymd(2026, 7, 6));
+
+    // Observed dates can fall in the previous year: Saturday 2022-01-01 is observed on Friday 2021-12-31.
+    let new_year = ObservedRule::new(Box::new(MonthDayRule::new(Month::January, 1)), Observance::NearestWeekday);
+    let calendar = Calendar::new(Some(vec![Box::new(new_year)]), None);
+    assert!(calendar.is_rule_holiday(ymd(2021, 12, 31)));
+    assert!(!calendar.is_business_day(ymd(2021, 12, 31)));
+    assert_eq!(calendar.add_business_days(ymd(2021, 12, 30), 1), ymd(2022, 1, 3));
+    assert_eq!(calendar.substract_business_days(ymd(2022, 1, 3), 1), ymd(2021, 12, 30));
+    assert_eq!(calendar.business_days_between(ymd(2021, 12, 27), ymd(2022, 1, 7)), 8);
+    calendar.build_business_day_index(2022, 2022);
+    assert_eq!(calendar.add_business_days(ymd(2021, 12, 30), 1), ymd(2022, 1, 3));
+
+    // UK Christmas chain: Saturday 25th and Sunday 26th are observed on Monday 27th and Tuesday 28th.
+    let christmas = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 25)), vec![]);
+    let boxing_day = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 26)), vec![Box::new(christmas.clone())]);
+    assert_eq!(christmas.get_date(2021), Some(ymd(2021, 12, 27)));
+    assert_eq!(boxing_day.get_date(2021), Some(ymd(2021, 12, 28)));
+    // Sunday 25th is observed on Tuesday 27th, as Monday 26th is Boxing Day.
+    let christmas = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::December, 25)), vec![Box::new(MonthDayRule::new(Month::December, 26))]);
+    assert_eq!(christmas.get_date(2022), Some(ymd(2022, 12, 27)));
+    assert_eq!(christmas.get_date(2024), Some(ymd(2024, 12, 25)));
+
+    // A weekday taken by a preceding holiday moves too: Easter Monday 2021 was on the 5th of April.
+    let taken_weekday = SubstituteDayRule::new(Box::new(MonthDayRule::new(Month::April, 5)), vec![Box::new(EasterOffsetRule::new(1))]);
+    assert_eq!(taken_weekday.get_dates(2021), vec![ymd(2021, 4, 6)]);
+    assert_eq!(taken_weekday.get_dates(2022), vec![ymd(2022, 4, 5)]);
+}
+
+#[test]
+fn removed_holidays() {
+    // Removing a rule generated holiday opens the calendar that day.
+    let mut ny = get_ny_calendar(None, None, None);
+    let christmas: NaiveDate = ymd(2024, 12, 25);
+    assert!(ny.is_rule_holiday(christmas));
+    assert_eq!(ny.add_business_days(ymd(2024, 12, 24), 1), ymd(2024, 12, 26));
+    ny.remove_holiday(christmas);
+    assert!(ny.is_business_day(christmas));
+    assert!(ny.is_removed_holiday(christmas));
+    assert_eq!(ny.get_removed_holidays(), vec![christmas]);
+    assert_eq!(ny.add_business_days(ymd(2024, 12, 24), 1), christmas);
+    ny.add_holidays_with_rules(2024, 2024);
+    assert!(!ny.is_explicit_holiday(christmas));
+    assert!(ny.is_business_day(christmas));
+
+    // Adding it back undoes the removal.
+    ny.add_holiday(christmas);
+    assert!(!ny.is_removed_holiday(christmas));
+    assert!(ny.get_removed_holidays().is_empty());
+    assert!(!ny.is_business_day(christmas));
+    assert_eq!(ny.add_business_days(ymd(2024, 12, 24), 1), ymd(2024, 12, 26));
+
+    // A removal on one side of a union survives only if the other calendar is open that day.
+    let mut london = get_london_calendar(None, None, None);
+    london.remove_holiday(christmas);
+    london.remove_holiday(ymd(2024, 12, 26));
+    let union: Calendar = london.combine(get_ny_calendar(None, None, None));
+    assert!(!union.is_business_day(christmas));
+    assert!(union.is_business_day(ymd(2024, 12, 26)));
+    assert!(union.is_removed_holiday(ymd(2024, 12, 26)));
+    assert!(!union.is_removed_holiday(christmas));
+
+    // The same holds when one side is a composition.
+    let nested: Calendar = london.combine(get_ny_calendar(None, None, None) & get_target_calendar(None, None, None));
+    assert!(!nested.is_business_day(christmas));
+    assert!(nested.is_business_day(ymd(2024, 12, 26)));
+}
+
+fn naive_add_business_days(calendar: &Calendar, t: NaiveDate, business_days: u32) -> NaiveDate {
+    let mut date: NaiveDate = t;
+    let mut count: u32 = 0;
+    while count < business_days {
+        date = date.succ_opt().unwrap();
+        if calendar.is_business_day(date) {
+            count += 1;
+        }
+    }
+    date
+}
+
+fn naive_substract_business_days(calendar: &Calendar, t: NaiveDate, business_days: u32) -> NaiveDate {
+    let mut date: NaiveDate = t;
+    let mut count: u32 = 0;
+    while count < business_days {
+        date = date.pred_opt().unwrap();
+        if calendar.is_business_day(date) {
+            count += 1;
+        }
+    }
+    date
+}
+
+#[test]
+fn business_day_index() {
+    let mut calendar = get_ny_calendar(None, None, None);
+    calendar.build_business_day_index(2020, 2021);
+
+    // Queries past the end of the index extend it.
+    let start: NaiveDate = ymd(2021, 12, 20);
+    for business_days in [1, 10, 300, 2600] {
+        assert_eq!(calendar.add_business_days(start, business_days), naive_add_business_days(&calendar, start, business_days));
+    }
+    let far: NaiveDate = ymd(2150, 6, 30);
+    assert_eq!(calendar.add_business_days(far, 5), naive_add_business_days(&calendar, far, 5));
+
+    // And so do queries before its start.
+    calendar.build_business_day_index(2020, 2021);
+    let start: NaiveDate = ymd(2020, 1, 10);
+    for business_days in [1, 10, 300, 2600] {
+        assert_eq!(calendar.substract_business_days(start, business_days), naive_substract_business_days(&calendar, start, business_days));
+    }
+    calendar.build_business_day_index(2020, 2021);
+    let early: NaiveDate = ymd(1950, 3, 1);
+    assert_eq!(calendar.add_business_days(early, 5), naive_add_business_days(&calendar, early, 5));
+    let business_days: i64 = ymd(2000, 1, 3).iter_days().take_while(|date| *date < ymd(2021, 1, 4))
+        .filter(|date| calendar.is_business_day(*date)).count() as i64;
+    calendar.build_business_day_index(2020, 2021);
+    assert_eq!(calendar.business_days_between(ymd(2021, 1, 4), ymd(2000, 1, 3)), -business_days);
+    assert_eq!(calendar.business_days_between(ymd(2000, 1, 3), ymd(2021, 1, 4)), business_days);
+
+    // Adding or removing holidays invalidates the index.
+    let day: NaiveDate = ymd(2024, 3, 5);
+    assert_eq!(calendar.add_business_days(ymd(2024, 3, 4), 1), day);
+    calendar.add_holiday(day);
+    assert_eq!(calendar.add_business_days(ymd(2024, 3, 4), 1), ymd(2024, 3, 6));
+    assert_eq!(calendar.business_days_between(ymd(2024, 3, 4), ymd(2024, 3, 8)), 3);
+    calendar.remove_holiday(day);
+    assert_eq!(calendar.add_business_days(ymd(2024, 3, 4), 1), day);
+    assert_eq!(calendar.business_days_between(ymd(2024, 3, 4), ymd(2024, 3, 8)), 4);
+    calendar.add_holidays_from_vec(vec![day]);
+    assert_eq!(calendar.substract_business_days(ymd(2024, 3, 6), 1), ymd(2024, 3, 4));
+}
+
+#[test]
+fn business_day_index_concurrent_readers() {
+    let calendar: Calendar = get_london_calendar(None, None, None);
+    let expected: Vec<(NaiveDate, NaiveDate)> = (0..8).map(|i| {
+        let start: NaiveDate = ymd(2000 + 20 * i, 6, 15);
+        (naive_add_business_days(&calendar, start, 1000), naive_substract_business_days(&calendar, start, 1000))
+    }).collect();
+
+    // All threads start with an empty index and extend it at the same time, in different directions.
+    std::thread::scope(|scope| {
+        for (i, (after, before)) in expected.iter().enumerate() {
+            let calendar: &Calendar = &calendar;
+            scope.spawn(move || {
+                let start: NaiveDate = ymd(2000 + 20 * i as i32, 6, 15);
+                for _ in 0..20 {
+                    assert_eq!(calendar.add_business_days(start, 1000), *after);
+                    assert_eq!(calendar.substract_business_days(start, 1000), *before);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn business_day_index_near_chrono_limits() {
+    let calendar = get_ny_calendar(None, None, None);
+    let late: NaiveDate = ymd(NaiveDate::MAX.year() - 5, 6, 15);
+    assert_eq!(calendar.add_business_days(late, 5), naive_add_business_days(&calendar, late, 5));
+    assert_eq!(calendar.checked_add_business_days(late, 2000), None);
+    assert_eq!(calendar.checked_add_business_days(late, u32::MAX), None);
+    // The index covers a single range of years, so the other end uses its own calendar.
+    let calendar = get_ny_calendar(None, None, None);
+    let early: NaiveDate = ymd(NaiveDate::MIN.year() + 5, 6, 15);
+    assert_eq!(calendar.substract_business_days(early, 5), naive_substract_business_days(&calendar, early, 5));
+    assert_eq!(calendar.checked_substract_business_days(early, 2000), None);
+    assert_eq!(calendar.checked_substract_business_days(NaiveDate::MIN, 1), None);
+    assert_eq!(calendar.checked_add_business_days(early, 0), Some(early));
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn add_business_days_out_of_range() {
+    get_ny_calendar(None, None, None).add_business_days(NaiveDate::MAX, 1);
+}
+
+#[test]
+fn tabulated_rules_outside_their_tables() {
+    // Lunar tables cover 1912 to 2100, Singapore announced dates end in 2026. Outside them, those holidays are simply missing.
+    let calendars: [Calendar; 4] = [
+        get_hong_kong_calendar(None, None, None),
+        get_singapore_calendar(None, None, None),
+        get_shanghai_calendar(None, None, None),
+        get_riyadh_calendar(None, None, None),
+    ];
+    for calendar in &calendars {
+        for year in [1900, 1911, 2030, 2101, 2150] {
+            weekday_holidays(calendar, year);
+        }
+        let early: NaiveDate = ymd(1905, 6, 15);
+        assert_eq!(calendar.add_business_days(early, 260), naive_add_business_days(calendar, early, 260));
+        let late: NaiveDate = ymd(2150, 6, 15);
+        assert_eq!(calendar.substract_business_days(late, 260), naive_substract_business_days(calendar, late, 260));
+    }
+    // Rule holidays are still there (Ching Ming on Sunday the 5th of April is observed on Monday).
+    check_holidays(&calendars[0], 2150, &[(1, 1), (4, 6), (4, 10), (4, 13), (5, 1), (7, 1), (10, 1), (12, 25)]);
+
+    let buddhas_birthday = SubstituteDayRule::new_with_weekend(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 4, 8)), vec![], &[Weekday::Sun]);
+    assert!(buddhas_birthday.get_dates(2150).is_empty());
+    assert_eq!(buddhas_birthday.get_date(2150), None);
+    assert_eq!(buddhas_birthday.get_date(2024), Some(ymd(2024, 5, 15)));
+    assert_eq!(LunarDateRule::new(LunarCalendar::Chinese, 4, 8).get_date(2150), None);
+    assert_eq!(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), 0, 3).get_date(2150), None);
+    // Multi-day holidays are substituted as in calendars: Lunar New Year 2023 started on a Sunday, moved after the third day.
+    let lunar_new_year = SubstituteDayRule::new_with_weekend(Box::new(DaySpanRule::new(Box::new(LunarDateRule::new(LunarCalendar::Chinese, 1, 1)), 0, 3)), vec![], &[Weekday::Sun]);
+    assert_eq!(lunar_new_year.get_dates(2023), vec![ymd(2023, 1, 23), ymd(2023, 1, 24), ymd(2023, 1, 25)]);
+    assert_eq!(lunar_new_year.get_date(2023), Some(ymd(2023, 1, 23)));
+    let limited = SubstituteDayRule::new(Box::new(YearRangeRule::new(Box::new(MonthDayRule::new(Month::August, 11)), Some(2016), None)), vec![]);
+    assert!(limited.get_dates(2015).is_empty());
+    assert_eq!(limited.get_date(2015), None);
+    assert_eq!(limited.get_date(2016), Some(ymd(2016, 8, 11)));
+}
+
+#[test]
+fn rules_without_a_date_in_a_year() {
+    // Labour Day and Ascension Day are only two days apart when Easter falls on the 25th of March.
+    let bridge = BridgeDayRule::new(Box::new(MonthDayRule::new(Month::May, 1)), Box::new(EasterOffsetRule::new(39)));
+    assert_eq!(bridge.get_date(2035), Some(ymd(2035, 5, 2)));
+    assert_eq!(bridge.get_date(2025), None);
+    assert!(bridge.get_dates(2025).is_empty());
+    assert_eq!(MonthDayRule::new(Month::February, 29).get_date(2023), None);
+    assert_eq!(MonthDayRule::new(Month::February, 29).get_date(2024), Some(ymd(2024, 2, 29)));
+    // Two holidays observed on the same Monday give a single date.
+    let observed = ObservedRule::new(Box::new(DaySpanRule::new(Box::new(MonthDayRule::new(Month::December, 25)), 0, 2)), Observance::SundayToMonday);
+    assert_eq!(observed.get_dates(2022), vec![ymd(2022, 12, 26)]);
+}
@@ -0,0 +1,134 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+type Ymd = (i32, u32, u32);
+
+// (date, following, modified following, preceding, modified preceding) on the NY calendar.
+const CASES: [(Ymd, Ymd, Ymd, Ymd, Ymd); 11] = [
+    ((2024, 5, 15), (2024, 5, 15), (2024, 5, 15), (2024, 5, 15), (2024, 5, 15)), // business day
+    ((2024, 5, 31), (2024, 5, 31), (2024, 5, 31), (2024, 5, 31), (2024, 5, 31)), // business day at month end
+    ((2024, 6, 1), (2024, 6, 3), (2024, 6, 3), (2024, 5, 31), (2024, 6, 3)), // Saturday at month start
+    ((2024, 3, 29), (2024, 4, 1), (2024, 3, 28), (2024, 3, 28), (2024, 3, 28)), // Good Friday at month end
+    ((2024, 6, 19), (2024, 6, 20), (2024, 6, 20), (2024, 6, 18), (2024, 6, 18)), // Juneteenth
+    ((2024, 8, 31), (2024, 9, 3), (2024, 8, 30), (2024, 8, 30), (2024, 8, 30)), // Saturday before Labor Day
+    ((2023, 12, 30), (2024, 1, 2), (2023, 12, 29), (2023, 12, 29), (2023, 12, 29)), // Saturday before New Year's Day
+    ((2022, 12, 25), (2022, 12, 27), (2022, 12, 27), (2022, 12, 23), (2022, 12, 23)), // Christmas on Sunday, observed on Monday
+    ((2021, 7, 4), (2021, 7, 6), (2021, 7, 6), (2021, 7, 2), (2021, 7, 2)), // Independence Day on Sunday, observed on Monday
+    ((2025, 11, 27), (2025, 11, 28), (2025, 11, 28), (2025, 11, 26), (2025, 11, 26)), // Thanksgiving
+    ((2024, 11, 30), (2024, 12, 2), (2024, 11, 29), (2024, 11, 29), (2024, 11, 29)), // Saturday at month end
+];
+
+#[test]
+fn adjusters_on_ny_calendar() {
+    let ny = get_ny_calendar(None, None, None);
+    let f = Following::new(&ny);
+    let mf = ModifiedFollowing::new(&ny);
+    let p = Preceding::new(&ny);
+    let mp = ModifiedPreceding::new(&ny);
+    for (date, fd, mfd, pd, mpd) in CASES {
+        let date = ymd(date.0, date.1, date.2);
+        assert_eq!(f.adjust(date), ymd(fd.0, fd.1, fd.2), "Following {}", date);
+        assert_eq!(mf.adjust(date), ymd(mfd.0, mfd.1, mfd.2), "ModifiedFollowing {}", date);
+        assert_eq!(p.adjust(date), ymd(pd.0, pd.1, pd.2), "Preceding {}", date);
+        assert_eq!(mp.adjust(date), ymd(mpd.0, mpd.1, mpd.2), "ModifiedPreceding {}", date);
+    }
+}
+
+#[test]
+fn adjusters_keep_business_days() {
+    let ny = get_ny_calendar(None, None, None);
+    let adjusters: Vec<Box<dyn DateAdjustingMethod>> = vec![
+        Box::new(Following::new(&ny)),
+        Box::new(ModifiedFollowing::new(&ny)),
+        Box::new(Preceding::new(&ny)),
+        Box::new(ModifiedPreceding::new(&ny)),
+    ];
+    for date in ymd(2020, 1, 1).iter_days().take(2000).filter(|d| ny.is_business_day(*d)) {
+        for adjuster in &adjusters {
+            assert_eq!(adjuster.adjust(date), date);
+        }
+    }
+}
+
+#[test]
+fn tenor_with_modified_following() {
+    let ny = get_ny_calendar(None, None, None);
+    let mf = ModifiedFollowing::new(&ny);
+    let tenor = Tenor::from_str_opt("1y").unwrap();
+    assert_eq!(tenor.add_to_date(ymd(2024, 2, 29), Some(&mf)).unwrap(), ymd(2025, 2, 28));
+    assert_eq!(tenor.add_to_date(ymd(2024, 5, 31), Some(&mf)).unwrap(), ymd(2025, 5, 30));
+    assert_eq!(tenor.add_to_date(ymd(2023, 3, 23), Some(&mf)).unwrap(), ymd(2024, 3, 25));
+}
+
+#[test]
+fn additional_conventions_on_ny_calendar() {
+    let ny = get_ny_calendar(None, None, None);
+    let nearest = Nearest::new(&ny);
+    assert_eq!(nearest.adjust(ymd(2024, 6, 1)), ymd(2024, 5, 31)); // Saturday
+    assert_eq!(nearest.adjust(ymd(2024, 6, 2)), ymd(2024, 6, 3)); // Sunday
+    assert_eq!(nearest.adjust(ymd(2024, 6, 19)), ymd(2024, 6, 20)); // Wednesday holiday, tie goes to following
+    assert_eq!(nearest.adjust(ymd(2024, 5, 15)), ymd(2024, 5, 15));
+
+    assert_eq!(Unadjusted.adjust(ymd(2024, 6, 1)), ymd(2024, 6, 1));
+
+    let hmmf = HalfMonthModifiedFollowing::new(&ny);
+    assert_eq!(hmmf.adjust(ymd(2024, 6, 15)), ymd(2024, 6, 14)); // Saturday 15th
+    assert_eq!(hmmf.adjust(ymd(2024, 6, 16)), ymd(2024, 6, 17)); // Sunday 16th
+    assert_eq!(hmmf.adjust(ymd(2024, 8, 31)), ymd(2024, 8, 30));
+    assert_eq!(hmmf.adjust(ymd(2024, 6, 1)), ymd(2024, 6, 3));
+}
+
+#[test]
+fn floating_rate_note_keeps_end_of_month() {
+    let ny = get_ny_calendar(None, None, None);
+    let frn = FloatingRateNote::new(&ny);
+    // 2024-08-30 is the last business day of August, so later dates go to month end.
+    let unadjusted = vec![ymd(2024, 5, 30), ymd(2024, 8, 30), ymd(2024, 11, 30), ymd(2025, 2, 28), ymd(2025, 5, 30)];
+    let adjusted = frn.adjust_schedule(&unadjusted);
+    assert_eq!(adjusted, vec![ymd(2024, 5, 30), ymd(2024, 8, 30), ymd(2024, 11, 29), ymd(2025, 2, 28), ymd(2025, 5, 30)]);
+
+    let unadjusted = vec![ymd(2024, 1, 31), ymd(2024, 4, 30), ymd(2024, 6, 30), ymd(2024, 8, 31)];
+    let adjusted = frn.adjust_schedule(&unadjusted);
+    assert_eq!(adjusted, vec![ymd(2024, 1, 31), ymd(2024, 4, 30), ymd(2024, 6, 28), ymd(2024, 8, 30)]);
+
+    // The bound convention keeps the end of month alignment too.
+    let bound = BusinessDayConvention::FloatingRateNote.bind(&ny);
+    assert_eq!(bound.adjust_schedule(&unadjusted), adjusted);
+    assert_eq!(bound.adjust(ymd(2024, 8, 31)), ymd(2024, 8, 30));
+
+    let frn_eom = FloatingRateNote::new_end_of_month(&ny);
+    assert_eq!(frn_eom.adjust(ymd(2024, 4, 15)), ymd(2024, 4, 30));
+    assert_eq!(frn_eom.adjust(ymd(2024, 3, 15)), ymd(2024, 3, 28)); // Good Friday on the 29th
+}
+
+#[test]
+fn business_day_convention_parsing_and_binding() {
+    let cases = [
+        ("MF", BusinessDayConvention::ModifiedFollowing),
+        ("Modified Following", BusinessDayConvention::ModifiedFollowing),
+        ("MODFOLLOWING", BusinessDayConvention::ModifiedFollowing),
+        ("F", BusinessDayConvention::Following),
+        ("following", BusinessDayConvention::Following),
+        ("P", BusinessDayConvention::Preceding),
+        ("MODPRECEDING", BusinessDayConvention::ModifiedPreceding),
+        ("NONE", BusinessDayConvention::Unadjusted),
+        ("NEAREST", BusinessDayConvention::Nearest),
+        ("FRN", BusinessDayConvention::FloatingRateNote),
+        ("half_month_modified_following", BusinessDayConvention::HalfMonthModifiedFollowing),
+    ];
+    for (input, convention) in cases {
+        assert_eq!(input.parse::<BusinessDayConvention>(), Ok(convention));
+        assert_eq!(convention.to_string().parse::<BusinessDayConvention>(), Ok(convention));
+    }
+    assert!("MFF".parse::<BusinessDayConvention>().is_err());
+
+    let ny = get_ny_calendar(None, None, None);
+    let mf = BusinessDayConvention::ModifiedFollowing.bind(&ny);
+    assert_eq!(mf.adjust(ymd(2024, 8, 31)), ymd(2024, 8, 30));
+    let tenor = Tenor::from_str_opt("1y").unwrap();
+    assert_eq!(tenor.add_to_date(ymd(2023, 3, 23), Some(mf.as_ref())).unwrap(), ymd(2024, 3, 25));
+}
@@ -0,0 +1,106 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+type Ymd = (i32, u32, u32);
+
+// ISDA 30/360 examples. Day counts for: Bond Basis, US, 30E/360, 30E/360 ISDA (termination date 2009-02-28), 30E+/360, German.
+const DAYS_30_CASES: [(Ymd, Ymd, [i32; 6]); 22] = [
+    ((2007, 1, 15), (2007, 1, 30), [15, 15, 15, 15, 15, 15]),
+    ((2007, 1, 15), (2007, 2, 15), [30, 30, 30, 30, 30, 30]),
+    ((2007, 1, 15), (2007, 7, 15), [180, 180, 180, 180, 180, 180]),
+    ((2007, 9, 30), (2008, 3, 31), [180, 180, 180, 180, 181, 180]),
+    ((2007, 9, 30), (2007, 10, 31), [30, 30, 30, 30, 31, 30]),
+    ((2007, 9, 30), (2008, 9, 30), [360, 360, 360, 360, 360, 360]),
+    ((2007, 1, 15), (2007, 1, 31), [16, 16, 15, 15, 16, 15]),
+    ((2007, 1, 31), (2007, 2, 28), [28, 28, 28, 30, 28, 30]),
+    ((2007, 2, 28), (2007, 3, 31), [33, 30, 32, 30, 33, 30]),
+    ((2006, 8, 31), (2007, 2, 28), [178, 178, 178, 180, 178, 180]),
+    ((2007, 2, 28), (2007, 8, 31), [183, 180, 182, 180, 183, 180]),
+    ((2007, 2, 14), (2007, 2, 28), [14, 14, 14, 16, 14, 16]),
+    ((2007, 2, 26), (2008, 2, 29), [363, 363, 363, 364, 363, 364]),
+    ((2008, 2, 29), (2009, 2, 28), [359, 360, 359, 358, 359, 360]),
+    ((2008, 2, 29), (2008, 3, 30), [31, 30, 31, 30, 31, 30]),
+    ((2008, 2, 29), (2008, 3, 31), [32, 30, 31, 30, 32, 30]),
+    ((2007, 2, 28), (2007, 3, 5), [7, 5, 7, 5, 7, 5]),
+    ((2007, 10, 31), (2007, 11, 28), [28, 28, 28, 28, 28, 28]),
+    ((2007, 8, 31), (2008, 2, 29), [179, 179, 179, 180, 179, 180]),
+    ((2008, 2, 29), (2008, 8, 31), [182, 180, 181, 180, 182, 180]),
+    ((2008, 8, 31), (2009, 2, 28), [178, 178, 178, 178, 178, 180]),
+    ((2009, 2, 28), (2009, 8, 31), [183, 180, 182, 180, 183, 180]),
+];
+
+#[test]
+fn days_30_360_family() {
+    let counters: [Days30Counter; 6] = [
+        Days30Counter{backend: Box::new(Days30BondCounter)},
+        Days30Counter{backend: Box::new(Days30UCounter)},
+        Days30Counter{backend: Box::new(Days30ECounter)},
+        Days30Counter{backend: Box::new(Days30EISDACounter::new(Some(ymd(2009, 2, 28))))},
+        Days30Counter{backend: Box::new(Days30EPlusCounter)},
+        Days30Counter{backend: Box::new(Days30GermanCounter)},
+    ];
+    let names = ["30/360", "30U/360", "30E/360", "30E/360 ISDA", "30E+/360", "30/360 German"];
+    for (s, e, expected_counts) in DAYS_30_CASES {
+        let (start, end) = (ymd(s.0, s.1, s.2), ymd(e.0, e.1, e.2));
+        for ((counter, name), expected) in counters.iter().zip(names).zip(expected_counts) {
+            assert_eq!(counter.day_count(start, end), expected, "{} {} {}", name, start, end);
+        }
+    }
+}
+
+#[test]
+fn days_30_e_isda_termination_date() {
+    let start = ymd(2008, 8, 31);
+    let end = ymd(2009, 2, 28);
+    let not_termination = Days30Counter{backend: Box::new(Days30EISDACounter::new(None))};
+    let termination = Days30Counter{backend: Box::new(Days30EISDACounter::new(Some(end)))};
+    assert_eq!(not_termination.day_count(start, end), 180);
+    assert_eq!(termination.day_count(start, end), 178);
+}
+
+// Business days in [start_date, end_date), negative if end_date is before start_date.
+fn naive_business_days(calendar: &Calendar, start_date: NaiveDate, end_date: NaiveDate) -> i32 {
+    let (from, to) = (start_date.min(end_date), start_date.max(end_date));
+    let count: i32 = from.iter_days().take_while(|date| *date < to).filter(|date| calendar.is_business_day(*date)).count() as i32;
+    if start_date <= end_date { count } else { -count }
+}
+
+#[test]
+fn business_day_counter() {
+    let calendar = get_ny_calendar(None, None, None);
+    let counter = BusinessDayCounter::new(&calendar);
+    let bus_252 = business_252(&calendar);
+    let cases: [(Ymd, Ymd, i32); 9] = [
+        // Christmas and New Year's Day inside the period, spanning two years.
+        ((2024, 12, 20), (2025, 1, 6), 9),
+        // Starts on a holiday and ends on a weekend.
+        ((2024, 7, 4), (2024, 7, 13), 6),
+        // Starts on a weekend and ends on a holiday.
+        ((2024, 8, 31), (2024, 9, 2), 0),
+        ((2024, 8, 31), (2024, 9, 3), 0),
+        ((2024, 8, 31), (2024, 9, 4), 1),
+        // Reversed periods are negative.
+        ((2025, 1, 6), (2024, 12, 20), -9),
+        ((2024, 7, 5), (2024, 7, 5), 0),
+        // Several years.
+        ((2019, 3, 15), (2024, 11, 28), 1423),
+        ((2024, 11, 28), (2019, 3, 15), -1423),
+    ];
+    for (s, e, expected) in cases {
+        let (start, end) = (ymd(s.0, s.1, s.2), ymd(e.0, e.1, e.2));
+        assert_eq!(naive_business_days(&calendar, start, end), expected, "{} {}", start, end);
+        assert_eq!(counter.day_count(start, end), expected, "{} {}", start, end);
+        assert_eq!(bus_252.time_fraction(start, end), expected as f64 / 252.0, "{} {}", start, end);
+    }
+    let mut start: NaiveDate = ymd(2023, 12, 1);
+    while start < ymd(2024, 2, 1) {
+        let end: NaiveDate = start + chrono::Duration::days(45);
+        assert_eq!(counter.day_count(start, end), naive_business_days(&calendar, start, end), "{} {}", start, end);
+        assert_eq!(counter.day_count(end, start), naive_business_days(&calendar, end, start), "{} {}", end, start);
+        start = start.succ_opt().unwrap();
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn imm_dates() {
+    assert_eq!(imm_date(2024, 3), ymd(2024, 3, 20));
+    assert_eq!(imm_date(2024, 6), ymd(2024, 6, 19));
+    assert_eq!(imm_date(2024, 9), ymd(2024, 9, 18));
+    assert_eq!(imm_date(2024, 12), ymd(2024, 12, 18));
+    assert_eq!(imm_date(2025, 3), ymd(2025, 3, 19));
+    assert!(is_imm_date(ymd(2024, 1, 17), false));
+    assert!(!is_imm_date(ymd(2024, 1, 17), true));
+    assert!(is_imm_date(ymd(2025, 6, 18), true));
+    assert!(!is_imm_date(ymd(2025, 6, 11), false));
+
+    assert_eq!(next_imm_date(ymd(2024, 1, 1), true), ymd(2024, 3, 20));
+    assert_eq!(next_imm_date(ymd(2024, 3, 20), true), ymd(2024, 6, 19));
+    assert_eq!(next_imm_date(ymd(2024, 12, 18), true), ymd(2025, 3, 19));
+    assert_eq!(next_imm_date(ymd(2024, 1, 1), false), ymd(2024, 1, 17));
+    assert_eq!(next_imm_date(ymd(2024, 1, 17), false), ymd(2024, 2, 21));
+    assert_eq!(previous_imm_date(ymd(2024, 3, 20), true), ymd(2023, 12, 20));
+    assert_eq!(previous_imm_date(ymd(2024, 3, 21), true), ymd(2024, 3, 20));
+    assert_eq!(previous_imm_date(ymd(2024, 3, 20), false), ymd(2024, 2, 21));
+}
+
+#[test]
+fn imm_codes() {
+    assert_eq!(imm_code(ymd(2025, 3, 19)), Some("H5".to_string()));
+    assert_eq!(imm_code(ymd(2024, 12, 18)), Some("Z4".to_string()));
+    assert_eq!(imm_code(ymd(2024, 1, 17)), Some("F4".to_string()));
+    assert_eq!(imm_code(ymd(2024, 12, 19)), None);
+
+    let reference = ymd(2024, 1, 1);
+    assert_eq!(imm_date_from_code("H5", reference), Ok(ymd(2025, 3, 19)));
+    assert_eq!(imm_date_from_code("Z4", reference), Ok(ymd(2024, 12, 18)));
+    assert_eq!(imm_date_from_code("m25", reference), Ok(ymd(2025, 6, 18)));
+    assert_eq!(imm_date_from_code("Z24", ymd(2020, 1, 1)), Ok(ymd(2024, 12, 18)));
+    // On or after the reference date, otherwise the next cycle.
+    assert_eq!(imm_date_from_code("H4", ymd(2024, 3, 20)), Ok(ymd(2024, 3, 20)));
+    assert_eq!(imm_date_from_code("H4", ymd(2024, 3, 21)), Ok(imm_date(2034, 3)));
+    assert_eq!(imm_date_from_code("H23", ymd(2024, 3, 21)), Ok(imm_date(2123, 3)));
+    let code: String = imm_code(ymd(2024, 9, 18)).unwrap();
+    assert_eq!(imm_date_from_code(&code, reference), Ok(ymd(2024, 9, 18)));
+
+    for input in ["", "H", "A5", "H123", "5H", "H-5", "HH"] {
+        assert_eq!(imm_date_from_code(input, reference), Err(ParseImmCodeError { input: input.to_string() }));
+    }
+}
+
+#[test]
+fn cds_dates() {
+    assert!(is_cds_date(ymd(2024, 6, 20)));
+    assert!(!is_cds_date(ymd(2024, 7, 20)));
+    assert_eq!(next_cds_date(ymd(2024, 3, 19)), ymd(2024, 3, 20));
+    assert_eq!(next_cds_date(ymd(2024, 3, 20)), ymd(2024, 6, 20));
+    assert_eq!(next_cds_date(ymd(2024, 12, 25)), ymd(2025, 3, 20));
+    assert_eq!(previous_cds_date(ymd(2024, 3, 20)), ymd(2023, 12, 20));
+    assert_eq!(previous_cds_date(ymd(2024, 3, 21)), ymd(2024, 3, 20));
+
+    // Semiannual roll: on-the-run maturities move on 20 March and 20 September.
+    assert_eq!(cds_roll_date(ymd(2024, 3, 19)), ymd(2023, 12, 20));
+    assert_eq!(cds_roll_date(ymd(2024, 3, 20)), ymd(2024, 6, 20));
+    assert_eq!(cds_roll_date(ymd(2024, 9, 19)), ymd(2024, 6, 20));
+    assert_eq!(cds_roll_date(ymd(2024, 9, 20)), ymd(2024, 12, 20));
+    assert_eq!(cds_roll_date(ymd(2024, 12, 31)), ymd(2024, 12, 20));
+    let five_years = Tenor::years(5);
+    assert_eq!(cds_maturity_date(ymd(2024, 3, 19), &five_years), Ok(ymd(2028, 12, 20)));
+    assert_eq!(cds_maturity_date(ymd(2024, 4, 10), &five_years), Ok(ymd(2029, 6, 20)));
+    assert_eq!(cds_maturity_date(ymd(2024, 10, 1), &five_years), Ok(ymd(2029, 12, 20)));
+}
+
+#[test]
+fn imm_rolled_schedules() {
+    let definition = ScheduleDefinition::new(ymd(2024, 2, 1), Maturity::Date(ymd(2025, 3, 19)), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Imm, None);
+    assert_eq!(definition.unadjusted_dates(), Ok(vec![
+        ymd(2024, 2, 1), ymd(2024, 3, 20), ymd(2024, 6, 19), ymd(2024, 9, 18), ymd(2024, 12, 18), ymd(2025, 3, 19),
+    ]));
+    let periods = definition.generate().unwrap();
+    assert_eq!((periods[0].reference_start, periods[0].reference_end), (ymd(2023, 12, 20), ymd(2024, 3, 20)));
+    assert!(periods[1..].iter().all(|p| !p.is_stub()));
+
+    let definition = ScheduleDefinition::new(ymd(2024, 3, 20), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Quarterly,
+                                             StubType::ShortLast, RollType::Imm, None);
+    assert_eq!(definition.maturity_date(), Ok(ymd(2025, 3, 19)));
+    let periods = definition.generate().unwrap();
+    assert_eq!(periods.len(), 4);
+    assert!(periods.iter().all(|p| !p.is_stub() && is_imm_date(p.unadjusted_end, true)));
+
+    let trade_date: NaiveDate = ymd(2024, 3, 20);
+    let maturity: NaiveDate = cds_maturity_date(trade_date, &Tenor::years(1)).unwrap();
+    let definition = ScheduleDefinition::new(trade_date, Maturity::Date(maturity), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Cds, None);
+    assert_eq!(definition.unadjusted_dates(), Ok(vec![
+        ymd(2024, 3, 20), ymd(2024, 6, 20), ymd(2024, 9, 20), ymd(2024, 12, 20), ymd(2025, 3, 20), ymd(2025, 6, 20),
+    ]));
+}
@@ -0,0 +1,227 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn unadjusted(periods: &[SchedulePeriod]) -> Vec<(NaiveDate, NaiveDate)> {
+    periods.iter().map(|p| (p.unadjusted_start, p.unadjusted_end)).collect()
+}
+
+fn schedule(effective_date: NaiveDate, maturity: Maturity, frequency: CouponFrequency, stub_type: StubType, roll_type: RollType) -> Vec<SchedulePeriod> {
+    ScheduleDefinition::new(effective_date, maturity, frequency, stub_type, roll_type, None).generate().unwrap()
+}
+
+#[test]
+fn coupon_frequencies() {
+    assert_eq!(CouponFrequency::Annually.tenor(), Some(Tenor::years(1)));
+    assert_eq!(CouponFrequency::Semiannually.tenor(), Some(Tenor::months(6)));
+    assert_eq!(CouponFrequency::Quarterly.tenor(), Some(Tenor::months(3)));
+    assert_eq!(CouponFrequency::Monthly.tenor(), Some(Tenor::months(1)));
+    assert_eq!(CouponFrequency::Zero.tenor(), None);
+    assert_eq!(CouponFrequency::Semiannually.periods_per_year(), 2);
+}
+
+#[test]
+fn regular_schedule() {
+    let periods = schedule(ymd(2024, 1, 15), Maturity::Tenor(Tenor::years(2)), CouponFrequency::Semiannually, StubType::ShortFirst, RollType::Standard);
+    assert_eq!(unadjusted(&periods), vec![
+        (ymd(2024, 1, 15), ymd(2024, 7, 15)),
+        (ymd(2024, 7, 15), ymd(2025, 1, 15)),
+        (ymd(2025, 1, 15), ymd(2025, 7, 15)),
+        (ymd(2025, 7, 15), ymd(2026, 1, 15)),
+    ]);
+    assert!(periods.iter().all(|p| !p.is_stub()));
+    // Same dates whichever side the stub would go.
+    let forward = schedule(ymd(2024, 1, 15), Maturity::Tenor(Tenor::years(2)), CouponFrequency::Semiannually, StubType::LongLast, RollType::Standard);
+    assert_eq!(periods, forward);
+
+    let zero = schedule(ymd(2024, 1, 15), Maturity::Date(ymd(2024, 11, 3)), CouponFrequency::Zero, StubType::ShortFirst, RollType::Standard);
+    assert_eq!(unadjusted(&zero), vec![(ymd(2024, 1, 15), ymd(2024, 11, 3))]);
+    assert!(!zero[0].is_stub());
+}
+
+#[test]
+fn short_first_stub() {
+    let periods = schedule(ymd(2024, 2, 10), Maturity::Date(ymd(2025, 1, 15)), CouponFrequency::Quarterly, StubType::ShortFirst, RollType::Standard);
+    assert_eq!(unadjusted(&periods), vec![
+        (ymd(2024, 2, 10), ymd(2024, 4, 15)),
+        (ymd(2024, 4, 15), ymd(2024, 7, 15)),
+        (ymd(2024, 7, 15), ymd(2024, 10, 15)),
+        (ymd(2024, 10, 15), ymd(2025, 1, 15)),
+    ]);
+    assert!(periods[0].is_stub());
+    assert_eq!((periods[0].reference_start, periods[0].reference_end), (ymd(2024, 1, 15), ymd(2024, 4, 15)));
+    assert!(periods[1..].iter().all(|p| !p.is_stub()));
+}
+
+#[test]
+fn long_first_stub() {
+    let periods = schedule(ymd(2024, 2, 10), Maturity::Date(ymd(2025, 1, 15)), CouponFrequency::Quarterly, StubType::LongFirst, RollType::Standard);
+    assert_eq!(unadjusted(&periods), vec![
+        (ymd(2024, 2, 10), ymd(2024, 7, 15)),
+        (ymd(2024, 7, 15), ymd(2024, 10, 15)),
+        (ymd(2024, 10, 15), ymd(2025, 1, 15)),
+    ]);
+    assert_eq!((periods[0].reference_start, periods[0].reference_end), (ymd(2024, 4, 15), ymd(2024, 7, 15)));
+
+    // Shorter than a period: a single stub, long or short.
+    let single = schedule(ymd(2024, 12, 1), Maturity::Date(ymd(2025, 1, 15)), CouponFrequency::Quarterly, StubType::LongFirst, RollType::Standard);
+    assert_eq!(unadjusted(&single), vec![(ymd(2024, 12, 1), ymd(2025, 1, 15))]);
+    assert_eq!((single[0].reference_start, single[0].reference_end), (ymd(2024, 10, 15), ymd(2025, 1, 15)));
+}
+
+#[test]
+fn short_last_stub() {
+    let periods = schedule(ymd(2024, 1, 15), Maturity::Date(ymd(2024, 12, 1)), CouponFrequency::Quarterly, StubType::ShortLast, RollType::Standard);
+    assert_eq!(unadjusted(&periods), vec![
+        (ymd(2024, 1, 15), ymd(2024, 4, 15)),
+        (ymd(2024, 4, 15), ymd(2024, 7, 15)),
+        (ymd(2024, 7, 15), ymd(2024, 10, 15)),
+        (ymd(2024, 10, 15), ymd(2024, 12, 1)),
+    ]);
+    assert!(periods[3].is_stub());
+    assert_eq!((periods[3].reference_start, periods[3].reference_end), (ymd(2024, 10, 15), ymd(2025, 1, 15)));
+}
+
+#[test]
+fn long_last_stub() {
+    let periods = schedule(ymd(2024, 1, 15), Maturity::Date(ymd(2024, 12, 1)), CouponFrequency::Quarterly, StubType::LongLast, RollType::Standard);
+    assert_eq!(unadjusted(&periods), vec![
+        (ymd(2024, 1, 15), ymd(2024, 4, 15)),
+        (ymd(2024, 4, 15), ymd(2024, 7, 15)),
+        (ymd(2024, 7, 15), ymd(2024, 12, 1)),
+    ]);
+    assert!(periods[2].is_stub());
+    assert_eq!((periods[2].reference_start, periods[2].reference_end), (ymd(2024, 7, 15), ymd(2024, 10, 15)));
+}
+
+#[test]
+fn roll_types() {
+    // Dates are rolled from the anchor, a 30th of November does not drag later dates to the 30th.
+    let standard = schedule(ymd(2024, 8, 31), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Quarterly, StubType::ShortLast, RollType::Standard);
+    assert_eq!(unadjusted(&standard), vec![
+        (ymd(2024, 8, 31), ymd(2024, 11, 30)),
+        (ymd(2024, 11, 30), ymd(2025, 2, 28)),
+        (ymd(2025, 2, 28), ymd(2025, 5, 31)),
+        (ymd(2025, 5, 31), ymd(2025, 8, 31)),
+    ]);
+
+    let end_of_month = schedule(ymd(2024, 2, 29), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Quarterly, StubType::ShortFirst, RollType::EndOfMonth);
+    assert_eq!(unadjusted(&end_of_month), vec![
+        (ymd(2024, 2, 29), ymd(2024, 5, 31)),
+        (ymd(2024, 5, 31), ymd(2024, 8, 31)),
+        (ymd(2024, 8, 31), ymd(2024, 11, 30)),
+        (ymd(2024, 11, 30), ymd(2025, 2, 28)),
+    ]);
+    let not_end_of_month = schedule(ymd(2024, 2, 29), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Quarterly, StubType::ShortFirst, RollType::Standard);
+    assert_eq!(unadjusted(&not_end_of_month), vec![
+        (ymd(2024, 2, 29), ymd(2024, 5, 28)),
+        (ymd(2024, 5, 28), ymd(2024, 8, 28)),
+        (ymd(2024, 8, 28), ymd(2024, 11, 28)),
+        (ymd(2024, 11, 28), ymd(2025, 2, 28)),
+    ]);
+    assert!(not_end_of_month[0].is_stub());
+}
+
+#[test]
+fn adjusted_dates() {
+    let ny = get_ny_calendar(None, None, None);
+    let mf = ModifiedFollowing::new(&ny);
+    let definition = ScheduleDefinition::new(ymd(2024, 6, 30), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Semiannually,
+                                             StubType::ShortFirst, RollType::EndOfMonth, Some(&mf));
+    let periods = definition.generate().unwrap();
+    assert_eq!(unadjusted(&periods), vec![(ymd(2024, 6, 30), ymd(2024, 12, 31)), (ymd(2024, 12, 31), ymd(2025, 6, 30))]);
+    assert_eq!(periods[0].adjusted_start, ymd(2024, 6, 28));
+    assert_eq!(periods[0].adjusted_end, ymd(2024, 12, 31));
+    assert_eq!(periods[1].adjusted_start, ymd(2024, 12, 31));
+    assert_eq!(periods[1].adjusted_end, ymd(2025, 6, 30));
+    assert!(periods.iter().all(|p| p.payment_date == p.adjusted_end));
+
+    let following = Following::new(&ny);
+    let definition = ScheduleDefinition::new(ymd(2024, 1, 4), Maturity::Date(ymd(2024, 12, 25)), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Standard, Some(&following));
+    let periods = definition.generate().unwrap();
+    assert_eq!(periods[0].adjusted_start, ymd(2024, 1, 4));
+    assert_eq!(periods[0].unadjusted_end, ymd(2024, 3, 25));
+    assert_eq!(periods[1].adjusted_end, ymd(2024, 6, 25));
+    assert_eq!(periods[3].unadjusted_end, ymd(2024, 12, 25));
+    assert_eq!(periods[3].adjusted_end, ymd(2024, 12, 26));
+
+    // FRN: 2024-08-30 is the last business day of August, so later dates go to month end.
+    let frn = BusinessDayConvention::FloatingRateNote.bind(&ny);
+    let definition = ScheduleDefinition::new(ymd(2024, 7, 30), Maturity::Date(ymd(2024, 11, 30)), CouponFrequency::Monthly,
+                                             StubType::ShortFirst, RollType::Standard, Some(frn.as_ref()));
+    let periods = definition.generate().unwrap();
+    let adjusted_ends: Vec<NaiveDate> = periods.iter().map(|p| p.adjusted_end).collect();
+    assert_eq!(adjusted_ends, vec![ymd(2024, 8, 30), ymd(2024, 9, 30), ymd(2024, 10, 31), ymd(2024, 11, 29)]);
+}
+
+#[test]
+fn schedule_errors() {
+    let definition = ScheduleDefinition::new(ymd(2024, 1, 15), Maturity::Date(ymd(2024, 1, 15)), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Standard, None);
+    assert_eq!(definition.generate(), Err(ScheduleError::MaturityNotAfterEffectiveDate { effective_date: ymd(2024, 1, 15), maturity_date: ymd(2024, 1, 15) }));
+    let definition = ScheduleDefinition::new(ymd(2024, 1, 15), Maturity::Tenor(Tenor::business_days(10)), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Standard, None);
+    assert!(matches!(definition.generate(), Err(ScheduleError::Tenor(TenorDateError::MissingCalendar { .. }))));
+}
+
+#[test]
+fn floating_leg_accrual_periods() {
+    let ny = get_ny_calendar(None, None, None);
+    let fixing_calendar = Calendar::new(None, Some(vec![ymd(2024, 7, 12)]));
+    let mf = ModifiedFollowing::new(&ny);
+    let act360 = DayCountConvention::Actual360.time_fraction_calc().unwrap();
+    let definition = ScheduleDefinition::new(ymd(2024, 1, 16), Maturity::Tenor(Tenor::years(1)), CouponFrequency::Quarterly,
+                                             StubType::ShortFirst, RollType::Standard, Some(&mf));
+    let periods = definition.accrual_periods(2, &ny, 2, &fixing_calendar, act360.as_ref()).unwrap();
+
+    let payment_dates: Vec<NaiveDate> = periods.iter().map(|p| p.payment_date).collect();
+    assert_eq!(payment_dates, vec![ymd(2024, 4, 18), ymd(2024, 7, 18), ymd(2024, 10, 18), ymd(2025, 1, 21)]); // MLK Day
+    let reset_dates: Vec<NaiveDate> = periods.iter().map(|p| p.reset_date).collect();
+    assert_eq!(reset_dates, vec![ymd(2024, 1, 16), ymd(2024, 4, 16), ymd(2024, 7, 16), ymd(2024, 10, 16)]);
+    // Fixing lag counted on the fixing calendar only (no Columbus Day, holiday on 2024-07-12).
+    let fixing_dates: Vec<NaiveDate> = periods.iter().map(|p| p.fixing_date).collect();
+    assert_eq!(fixing_dates, vec![ymd(2024, 1, 12), ymd(2024, 4, 12), ymd(2024, 7, 11), ymd(2024, 10, 14)]);
+    let year_fractions: Vec<f64> = periods.iter().map(|p| p.year_fraction).collect();
+    assert_eq!(year_fractions, vec![91.0 / 360.0, 91.0 / 360.0, 92.0 / 360.0, 92.0 / 360.0]);
+
+    let no_lags = definition.accrual_periods(0, &ny, 0, &fixing_calendar, act360.as_ref()).unwrap();
+    assert!(no_lags.iter().all(|p| p.payment_date == p.adjusted_end && p.fixing_date == p.adjusted_start));
+}
+
+#[test]
+fn icma_accrual_periods() {
+    let ny = get_ny_calendar(None, None, None);
+    let icma = ActualActualICMA::new(Some(2), false).unwrap();
+    let definition = ScheduleDefinition::new(ymd(2024, 2, 10), Maturity::Date(ymd(2025, 1, 15)), CouponFrequency::Semiannually,
+                                             StubType::ShortFirst, RollType::Standard, None);
+    let periods = definition.accrual_periods(0, &ny, 0, &ny, &icma).unwrap();
+    assert_eq!(periods.len(), 2);
+    assert!((periods[0].year_fraction - 156.0 / (2.0 * 182.0)).abs() < 1e-12);
+    assert!((periods[1].year_fraction - 0.5).abs() < 1e-12);
+
+    let definition = ScheduleDefinition::new(ymd(2024, 2, 10), Maturity::Date(ymd(2025, 1, 15)), CouponFrequency::Semiannually,
+                                             StubType::LongFirst, RollType::Standard, None);
+    let periods = definition.accrual_periods(0, &ny, 0, &ny, &icma).unwrap();
+    assert_eq!(periods.len(), 1);
+    assert!((periods[0].year_fraction - (0.5 + 156.0 / (2.0 * 182.0))).abs() < 1e-12);
+
+    // Reference periods are unadjusted. Following moves 2023-12-30 to 2024-01-02, the first period is 6 months and 3 days long.
+    let following = Following::new(&ny);
+    let definition = ScheduleDefinition::new(ymd(2023, 6, 30), Maturity::Date(ymd(2025, 6, 30)), CouponFrequency::Semiannually,
+                                             StubType::ShortFirst, RollType::Standard, Some(&following));
+    let inferred = definition.accrual_periods(0, &ny, 0, &ny, &ActualActualICMA::new(None, false).unwrap()).unwrap();
+    let semiannual = definition.accrual_periods(0, &ny, 0, &ny, &icma).unwrap();
+    assert_eq!((inferred[0].adjusted_start, inferred[0].adjusted_end), (ymd(2023, 6, 30), ymd(2024, 1, 2)));
+    assert!((inferred[0].year_fraction - (0.5 + 3.0 / (2.0 * 183.0))).abs() < 1e-12);
+    assert_eq!(inferred, semiannual);
+
+    // A zero coupon period of 10 months is not a regular reference period to infer the frequency from.
+    let zero = ScheduleDefinition::new(ymd(2024, 1, 15), Maturity::Date(ymd(2024, 11, 15)), CouponFrequency::Zero,
+                                       StubType::ShortFirst, RollType::Standard, None);
+    assert_eq!(zero.accrual_periods(0, &ny, 0, &ny, &ActualActualICMA::new(None, false).unwrap()).err(),
+               Some(ScheduleError::DayCount(DayCountError::InvalidReferencePeriod { reference_start: ymd(2024, 1, 15), reference_end: ymd(2024, 11, 15) })));
+}
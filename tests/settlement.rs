@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn spot_dates() {
+    let ny = get_ny_calendar(None, None, None);
+    assert_eq!(spot_date(ymd(2024, 7, 3), 2, &ny, None), ymd(2024, 7, 8)); // Independence Day
+    assert_eq!(spot_date(ymd(2024, 7, 2), 2, &ny, None), ymd(2024, 7, 5));
+    assert_eq!(spot_date(ymd(2024, 7, 2), 0, &ny, None), ymd(2024, 7, 2));
+    // With no lag, a holiday or weekend trade date settles on the next business day.
+    assert_eq!(spot_date(ymd(2024, 7, 4), 0, &ny, None), ymd(2024, 7, 5));
+    assert_eq!(spot_date(ymd(2024, 7, 6), 0, &ny, None), ymd(2024, 7, 8));
+    assert_eq!(spot_date(ymd(2024, 6, 12), 0, &Calendar::new(None, Some(vec![ymd(2024, 6, 12)])), Some(&ny)), ymd(2024, 6, 13));
+
+    // Lag counted on the non USD calendar, then moved off USD holidays.
+    let other = Calendar::new(None, Some(vec![ymd(2024, 6, 12)]));
+    assert_eq!(spot_date(ymd(2024, 6, 10), 2, &other, None), ymd(2024, 6, 13));
+    assert_eq!(spot_date(ymd(2024, 6, 17), 2, &other, None), ymd(2024, 6, 19));
+    assert_eq!(spot_date(ymd(2024, 6, 17), 2, &other, Some(&ny)), ymd(2024, 6, 20)); // Juneteenth
+    // USD holiday between trade and spot does not count as a lag day when counting on the joint calendar only.
+    let joint = Calendar::new(None, Some(vec![ymd(2024, 6, 12)])).combine(get_ny_calendar(None, None, None));
+    assert_eq!(spot_date(ymd(2024, 6, 18), 2, &joint, None), ymd(2024, 6, 21));
+    assert_eq!(spot_date(ymd(2024, 6, 18), 2, &other, Some(&ny)), ymd(2024, 6, 20));
+}
+
+#[test]
+fn settlement_lags() {
+    let lags = SettlementLags::fx_spot();
+    assert_eq!(lags.get_lag("USDCAD"), 1);
+    assert_eq!(lags.get_lag("usd/cad"), 1);
+    assert_eq!(lags.get_lag("CADUSD"), 1);
+    assert_eq!(lags.get_lag("EURUSD"), 2);
+
+    let mut lags = SettlementLags::new(2);
+    lags.set_lag("UST", 1);
+    assert_eq!(lags.get_lag("ust"), 1);
+    assert_eq!(lags.get_lag("BUND"), 2);
+
+    // Only currency pairs are matched in reverse.
+    lags.set_lag("BUND01", 1);
+    assert_eq!(lags.get_lag("bund01"), 1);
+    assert_eq!(lags.get_lag("D01BUN"), 2);
+    lags.set_currency_pair_lag("eur/gbp", 0);
+    assert_eq!(lags.get_lag("GBPEUR"), 0);
+    assert_eq!(lags.get_lag("EURGBP"), 0);
+
+    let ny = get_ny_calendar(None, None, None);
+    let rule = SettlementLags::fx_spot().rule("USD/CAD", &ny, None);
+    assert_eq!(rule.lag, 1);
+    assert_eq!(rule.settlement_date(ymd(2024, 7, 3)), ymd(2024, 7, 5));
+}
+
+#[test]
+#[should_panic]
+fn currency_pairs_are_two_currency_codes() {
+    SettlementLags::new(2).set_currency_pair_lag("BUND01", 1);
+}
@@ -0,0 +1,186 @@
+use chrono::{Datelike, NaiveDate};
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn components(tenor: &Tenor) -> (i32, i32, i32, i32, i32) {
+    (tenor.get_years(), tenor.get_months(), tenor.get_weeks(), tenor.get_days(), tenor.get_business_days())
+}
+
+#[test]
+fn tenor_grammar() {
+    let cases = [
+        ("1D", (0, 0, 0, 1, 0)),
+        ("3w", (0, 0, 3, 0, 0)),
+        (" 6M ", (0, 6, 0, 0, 0)),
+        ("100Y", (100, 0, 0, 0, 0)),
+        ("1Y3M", (1, 3, 0, 0, 0)),
+        ("2Y6M", (2, 6, 0, 0, 0)),
+        ("1Y2M3W4D", (1, 2, 3, 4, 0)),
+        ("-3M", (0, -3, 0, 0, 0)),
+        ("-1Y6M", (-1, -6, 0, 0, 0)),
+        ("+2W", (0, 0, 2, 0, 0)),
+        ("5B", (0, 0, 0, 0, 5)),
+        ("5BD", (0, 0, 0, 0, 5)),
+        ("ON", (0, 0, 0, 0, 1)),
+        ("TN", (0, 0, 0, 0, 1)),
+        ("SN", (0, 0, 0, 0, 1)),
+        ("SW", (0, 0, 1, 0, 0)),
+    ];
+    for (input, expected) in cases {
+        let tenor: Tenor = input.parse().unwrap();
+        assert_eq!(components(&tenor), expected, "{}", input);
+    }
+    assert_eq!("ON".parse::<Tenor>().unwrap().get_code(), Some(MoneyMarketCode::Overnight));
+    assert_eq!("SW".parse::<Tenor>().unwrap().get_code(), Some(MoneyMarketCode::SpotWeek));
+
+    for input in ["", "-", "M", "3", "3X", "1Y1Y", "1.5Y", "-ON", "99999999999M", "Y3"] {
+        assert!(input.parse::<Tenor>().is_err(), "{}", input);
+    }
+    let error = "3X".parse::<Tenor>().unwrap_err();
+    assert_eq!(error.input, "3X");
+    assert!(error.to_string().contains("unknown unit 'X'"));
+}
+
+#[test]
+fn tenor_add_to_date() {
+    let date = ymd(2024, 1, 31);
+    let add = |tenor: &str| tenor.parse::<Tenor>().unwrap().add_to_date(date, None).unwrap();
+    assert_eq!(add("1Y3M"), ymd(2025, 4, 30));
+    assert_eq!(add("15M"), ymd(2025, 4, 30));
+    assert_eq!(add("-3M"), ymd(2023, 10, 31));
+    assert_eq!(add("100Y"), ymd(2124, 1, 31));
+    assert_eq!(add("1M1W"), ymd(2024, 3, 7));
+
+    let ny = get_ny_calendar(None, None, None);
+    let add_bd = |tenor: &str, date: NaiveDate| tenor.parse::<Tenor>().unwrap().add_to_date_with_calendar(date, &ny, false, None).unwrap();
+    assert_eq!(add_bd("5BD", ymd(2024, 6, 14)), ymd(2024, 6, 24)); // Juneteenth
+    assert_eq!(add_bd("-2B", ymd(2024, 6, 20)), ymd(2024, 6, 17));
+    assert_eq!(add_bd("ON", ymd(2024, 7, 3)), ymd(2024, 7, 5));
+    assert_eq!(add_bd("SW", ymd(2024, 7, 3)), ymd(2024, 7, 10));
+}
+
+#[test]
+fn tenor_add_to_date_never_panics() {
+    let date = ymd(2024, 1, 31);
+    assert!(matches!("5B".parse::<Tenor>().unwrap().add_to_date(date, None), Err(TenorDateError::MissingCalendar { .. })));
+    let huge: Tenor = "2000000000Y".parse().unwrap();
+    assert!(matches!(huge.add_to_date(date, None), Err(TenorDateError::OutOfRange { .. })));
+    let huge: Tenor = "-2000000000D".parse().unwrap();
+    assert!(matches!(huge.add_to_date(date, None), Err(TenorDateError::OutOfRange { .. })));
+    let ny = get_ny_calendar(None, None, None);
+    let huge: Tenor = "2000000000B".parse().unwrap();
+    assert!(matches!(huge.add_to_date_with_calendar(date, &ny, false, None), Err(TenorDateError::OutOfRange { .. })));
+    let late: NaiveDate = ymd(NaiveDate::MAX.year() - 5, 6, 14);
+    let tenor: Tenor = "2000B".parse().unwrap();
+    assert!(matches!(tenor.add_to_date_with_calendar(late, &ny, false, None), Err(TenorDateError::OutOfRange { .. })));
+    assert!("5B".parse::<Tenor>().unwrap().add_to_date_with_calendar(late, &ny, false, None).is_ok());
+}
+
+#[test]
+fn tenor_display() {
+    for input in ["1D", "3W", "6M", "1Y", "1Y3M", "12M", "-3M", "-1Y6M", "5B", "ON", "TN", "SN", "SW", "1M-1D", "-1M-1D", "1Y2M3W4D5B"] {
+        let tenor: Tenor = input.parse().unwrap();
+        assert_eq!(tenor.to_string(), input);
+        assert_eq!(tenor.to_string().parse::<Tenor>().unwrap(), tenor);
+    }
+    assert_eq!("5BD".parse::<Tenor>().unwrap().to_string(), "5B");
+    assert_eq!(Tenor::new(0, 0, 0, 0, 0).to_string(), "0D");
+    assert_eq!(Tenor::new(-1, 1, 0, 0, 0).to_string(), "-1Y-1M");
+    assert_eq!("-1M-1D".parse::<Tenor>().unwrap(), Tenor::new(0, -1, 0, 1, 0));
+}
+
+#[test]
+fn tenor_normalization_and_equality() {
+    let tenor = |s: &str| s.parse::<Tenor>().unwrap();
+    assert_eq!(tenor("12M"), tenor("1Y"));
+    assert_eq!(tenor("18M"), tenor("1Y6M"));
+    assert_eq!(tenor("7D"), tenor("1W"));
+    assert_ne!(tenor("1M"), tenor("30D"));
+    assert_ne!(tenor("ON"), tenor("1B"));
+    assert_eq!(tenor("15M").normalized().to_string(), "1Y3M");
+    assert_eq!(tenor("10D").normalized().to_string(), "1W3D");
+    assert_eq!((tenor("1Y") - tenor("3M")).normalized().to_string(), "9M");
+    assert_eq!(tenor("12M").to_string(), "12M");
+
+    let mut pillars = std::collections::HashMap::new();
+    pillars.insert(tenor("1Y"), 0.05);
+    assert_eq!(pillars.get(&tenor("12M")), Some(&0.05));
+}
+
+#[test]
+fn tenor_arithmetic() {
+    let tenor = |s: &str| s.parse::<Tenor>().unwrap();
+    assert_eq!(tenor("1Y") + tenor("6M"), tenor("18M"));
+    assert_eq!((tenor("1Y") + tenor("6M")).to_string(), "1Y6M");
+    assert_eq!(tenor("1Y") - tenor("6M"), tenor("6M"));
+    assert_eq!(tenor("3M") * 4, tenor("1Y"));
+    assert_eq!(2 * tenor("1W"), tenor("14D"));
+    assert_eq!(-tenor("3M"), tenor("-3M"));
+    assert_eq!((tenor("ON") + tenor("1D")).get_code(), None);
+
+    let huge: Tenor = tenor("2000000000Y");
+    assert_eq!(huge.checked_add(huge), None);
+    assert_eq!(huge.checked_sub(-huge), None);
+    assert_eq!(huge.checked_mul(2), None);
+    assert_eq!(Tenor::days(i32::MIN).checked_neg(), None);
+    assert_eq!(huge.checked_add(tenor("1Y6M")), Some(tenor("2000000001Y6M")));
+    assert_eq!(tenor("3M").checked_mul(4), Some(tenor("1Y")));
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn tenor_arithmetic_overflow() {
+    let huge: Tenor = "2000000000Y".parse().unwrap();
+    let _ = huge + huge;
+}
+
+#[test]
+fn tenor_ordering() {
+    let tenor = |s: &str| s.parse::<Tenor>().unwrap();
+    assert!(tenor("1M") < tenor("2M"));
+    assert!(tenor("1Y") > tenor("11M"));
+    assert!(tenor("1M") < tenor("5W"));
+    assert!(tenor("1M") > tenor("27D"));
+    assert!(tenor("1Y") > tenor("360D"));
+    assert!(tenor("-1M") < tenor("1D"));
+    assert_eq!(tenor("1M").partial_cmp(&tenor("30D")), None);
+    assert_eq!(tenor("1Y").partial_cmp(&tenor("365D")), None);
+    assert_eq!(tenor("1B").partial_cmp(&tenor("1D")), None);
+    assert!(tenor("1B") < tenor("2B"));
+    assert_eq!(tenor("ON").partial_cmp(&tenor("TN")), None);
+
+    let mut pillars: Vec<Tenor> = ["10Y", "1M", "3M", "1W", "18M", "2Y", "6M", "1Y", "ON"].iter().map(|s| tenor(s)).collect();
+    pillars.retain(|t| t.get_code().is_none());
+    pillars.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let sorted: Vec<String> = pillars.iter().map(|t| t.to_string()).collect();
+    assert_eq!(sorted, vec!["1W", "1M", "3M", "6M", "1Y", "18M", "2Y", "10Y"]);
+}
+
+#[test]
+fn tenor_end_of_month_rolling() {
+    let tenor = |s: &str| s.parse::<Tenor>().unwrap();
+    let add_eom = |t: &str, date: NaiveDate| tenor(t).add_to_date_end_of_month(date, true, None).unwrap();
+    assert_eq!(add_eom("1M", ymd(2024, 4, 30)), ymd(2024, 5, 31));
+    assert_eq!(tenor("1M").add_to_date(ymd(2024, 4, 30), None).unwrap(), ymd(2024, 5, 30));
+    assert_eq!(add_eom("1M", ymd(2024, 1, 31)), ymd(2024, 2, 29));
+    assert_eq!(add_eom("1M", ymd(2023, 1, 31)), ymd(2023, 2, 28));
+    assert_eq!(add_eom("1M", ymd(2024, 2, 29)), ymd(2024, 3, 31));
+    assert_eq!(add_eom("1M", ymd(2023, 2, 28)), ymd(2023, 3, 31));
+    assert_eq!(add_eom("1Y", ymd(2023, 2, 28)), ymd(2024, 2, 29));
+    assert_eq!(add_eom("1Y", ymd(2024, 2, 29)), ymd(2025, 2, 28));
+    assert_eq!(add_eom("4Y", ymd(2020, 2, 29)), ymd(2024, 2, 29));
+    assert_eq!(add_eom("-1M", ymd(2024, 3, 31)), ymd(2024, 2, 29));
+    assert_eq!(add_eom("-3M", ymd(2024, 2, 29)), ymd(2023, 11, 30));
+    assert_eq!(add_eom("6M", ymd(2024, 2, 28)), ymd(2024, 8, 28)); // not a month end in a leap year
+    assert_eq!(add_eom("1M1W", ymd(2024, 4, 30)), ymd(2024, 6, 7));
+    assert_eq!(add_eom("1W", ymd(2024, 4, 30)), ymd(2024, 5, 7));
+
+    let ny = get_ny_calendar(None, None, None);
+    let mf = ModifiedFollowing::new(&ny);
+    // 2024-08-31 is a Saturday, modified following goes back to Friday.
+    assert_eq!(tenor("2M").add_to_date_end_of_month(ymd(2024, 6, 30), true, Some(&mf)).unwrap(), ymd(2024, 8, 30));
+    assert_eq!(tenor("1M").add_to_date_with_calendar(ymd(2024, 4, 30), &ny, true, None).unwrap(), ymd(2024, 5, 31));
+}
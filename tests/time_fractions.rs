@@ -0,0 +1,165 @@
+use chrono::NaiveDate;
+use dates::prelude::*;
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-12, "{} != {}", a, b);
+}
+
+#[test]
+fn day_count_convention_parsing() {
+    let cases = [
+        ("ACT/360", DayCountConvention::Actual360),
+        ("Actual/360", DayCountConvention::Actual360),
+        ("ACT/365F", DayCountConvention::Actual365Fixed),
+        ("Actual/365 (Fixed)", DayCountConvention::Actual365Fixed),
+        ("ACT/ACT ISDA", DayCountConvention::ActualActualISDA),
+        ("Actual/Actual (ISDA)", DayCountConvention::ActualActualISDA),
+        ("ACT/ACT ICMA", DayCountConvention::ActualActualICMA),
+        ("ACT/ACT AFB", DayCountConvention::ActualActualAFB),
+        ("ACT/364", DayCountConvention::Actual364),
+        ("ACT/365L", DayCountConvention::Actual365L),
+        ("NL/365", DayCountConvention::NoLeap365),
+        ("30/360", DayCountConvention::Thirty360BondBasis),
+        ("30U/360", DayCountConvention::Thirty360US),
+        ("30E/360", DayCountConvention::Thirty360E),
+        ("Eurobond Basis", DayCountConvention::Thirty360E),
+        ("30E/360 ISDA", DayCountConvention::Thirty360EISDA),
+        ("30E+/360", DayCountConvention::Thirty360EPlus),
+        ("30/360 German", DayCountConvention::Thirty360German),
+        ("bus/252", DayCountConvention::Business252),
+    ];
+    for (input, convention) in cases {
+        assert_eq!(input.parse::<DayCountConvention>(), Ok(convention));
+        assert_eq!(convention.to_string().parse::<DayCountConvention>(), Ok(convention));
+    }
+    assert!("ACT/999".parse::<DayCountConvention>().is_err());
+}
+
+#[test]
+fn day_count_convention_time_fractions() {
+    let start = ymd(2024, 1, 31);
+    let end = ymd(2024, 7, 31);
+    assert_close(DayCountConvention::Actual360.time_fraction_calc().unwrap().time_fraction(start, end), 182.0 / 360.0);
+    assert_close(DayCountConvention::Actual365Fixed.time_fraction_calc().unwrap().time_fraction(start, end), 182.0 / 365.0);
+    assert_close(DayCountConvention::ActualActualISDA.time_fraction_calc().unwrap().time_fraction(start, end), 182.0 / 366.0);
+    assert_close(DayCountConvention::Thirty360E.time_fraction_calc().unwrap().time_fraction(start, end), 0.5);
+    assert_close(DayCountConvention::Thirty360E.with_frequency(2).unwrap().time_fraction(start, end), 0.5);
+
+    // ACT/ACT ICMA needs the coupon frequency. Semiannual coupons on the 1st of March and September, the quasi coupon period is 182 days.
+    let icma = "ACT/ACT ICMA".parse::<DayCountConvention>().unwrap().with_frequency(2).unwrap();
+    assert_close(icma.time_fraction(ymd(2024, 1, 15), ymd(2024, 3, 1)), 46.0 / (182.0 * 2.0));
+    assert_close(icma.time_fraction(ymd(2023, 9, 1), ymd(2024, 3, 1)), 0.5);
+
+    let ny = get_ny_calendar(None, None, None);
+    // 2024-06-03 to 2024-06-10: 5 business days, 2024-06-19 to 2024-06-26: 4 business days (Juneteenth)
+    let bus252 = DayCountConvention::Business252.bind(&ny).unwrap();
+    assert_close(bus252.time_fraction(ymd(2024, 6, 3), ymd(2024, 6, 10)), 5.0 / 252.0);
+    assert_close(bus252.time_fraction(ymd(2024, 6, 19), ymd(2024, 6, 26)), 4.0 / 252.0);
+}
+
+#[test]
+fn day_count_errors() {
+    // Conventions parsed from trade data fail instead of panicking when they lack what they need.
+    let icma: DayCountConvention = "ACT/ACT ICMA".parse().unwrap();
+    let bus252: DayCountConvention = "BUS/252".parse().unwrap();
+    assert_eq!(icma.time_fraction_calc().err(), Some(DayCountError::MissingFrequency { convention: icma }));
+    assert_eq!(bus252.time_fraction_calc().err(), Some(DayCountError::MissingCalendar { convention: bus252 }));
+    assert_eq!(bus252.with_frequency(2).err(), Some(DayCountError::MissingCalendar { convention: bus252 }));
+    let ny = get_ny_calendar(None, None, None);
+    assert_eq!(icma.bind(&ny).err(), Some(DayCountError::MissingFrequency { convention: icma }));
+    assert_eq!(icma.with_frequency(0).err(), Some(DayCountError::InvalidFrequency { frequency: 0 }));
+    assert_eq!(icma.with_frequency(5).err(), Some(DayCountError::InvalidFrequency { frequency: 5 }));
+    assert!(ActualActualICMA::new(Some(7), false).is_err());
+    for frequency in [1, 2, 3, 4, 6, 12] {
+        assert!(icma.with_frequency(frequency).is_ok());
+    }
+
+    // Without a frequency, ACT/ACT ICMA needs a regular reference period.
+    let inferred = ActualActualICMA::new(None, false).unwrap();
+    assert_eq!(inferred.try_time_fraction(ymd(2024, 1, 1), ymd(2024, 1, 20)), Err(DayCountError::MissingReferencePeriod));
+    assert_eq!(inferred.try_time_fraction_with_reference(ymd(2024, 1, 1), ymd(2024, 1, 20), ymd(2023, 8, 1), ymd(2024, 1, 1)),
+               Err(DayCountError::InvalidReferencePeriod { reference_start: ymd(2023, 8, 1), reference_end: ymd(2024, 1, 1) }));
+    assert_eq!(inferred.try_time_fraction_with_reference(ymd(2023, 7, 1), ymd(2024, 1, 1), ymd(2023, 7, 1), ymd(2024, 1, 1)), Ok(0.5));
+    assert_eq!(DayCountConvention::Actual360.time_fraction_calc().unwrap().try_time_fraction(ymd(2024, 1, 1), ymd(2024, 1, 19)), Ok(0.05));
+}
+
+#[test]
+fn actual_actual_isda() {
+    let isda = ActualActualISDA::new();
+    assert_close(isda.time_fraction(ymd(2023, 7, 1), ymd(2024, 7, 1)), 184.0 / 365.0 + 182.0 / 366.0);
+    assert_close(isda.time_fraction(ymd(2023, 12, 31), ymd(2024, 1, 1)), 1.0 / 365.0);
+    assert_close(isda.time_fraction(ymd(2022, 7, 1), ymd(2025, 1, 1)), 184.0 / 365.0 + 2.0);
+    assert_close(isda.time_fraction(ymd(2024, 1, 1), ymd(2025, 1, 1)), 1.0);
+    // ISDA 2006 example: 2003-11-01 to 2004-05-01.
+    assert_close(isda.time_fraction(ymd(2003, 11, 1), ymd(2004, 5, 1)), 61.0 / 365.0 + 121.0 / 366.0);
+}
+
+// Examples from ISDA "EMU and market conventions: recent developments" (1998).
+#[test]
+fn actual_actual_icma() {
+    // Regular semiannual period.
+    let icma = ActualActualICMA::new(Some(2), false).unwrap();
+    assert_close(icma.time_fraction(ymd(2003, 11, 1), ymd(2004, 5, 1)), 0.5);
+
+    // Short first period, annual.
+    let icma = ActualActualICMA::new(Some(1), false).unwrap();
+    assert_close(icma.time_fraction(ymd(1999, 2, 1), ymd(1999, 7, 1)), 150.0 / 365.0);
+
+    // Long first period, annual.
+    assert_close(icma.time_fraction(ymd(2002, 8, 15), ymd(2003, 7, 15)), 334.0 / 365.0);
+
+    // Short first period, semiannual, month end coupons.
+    let icma = ActualActualICMA::new(Some(2), true).unwrap();
+    assert_close(icma.time_fraction(ymd(2000, 1, 30), ymd(2000, 6, 30)), 152.0 / 364.0);
+
+    // Long final period, quarterly, month end coupons.
+    let icma = ActualActualICMA::new(Some(4), true).unwrap();
+    let yf = icma.time_fraction_with_reference(ymd(1999, 11, 30), ymd(2000, 4, 30), ymd(1999, 11, 30), ymd(2000, 2, 29));
+    assert_close(yf, 0.25 + 61.0 / (92.0 * 4.0));
+
+    // Frequency inferred from the reference period.
+    let icma = ActualActualICMA::new(None, false).unwrap();
+    let yf = icma.time_fraction_with_reference(ymd(1999, 2, 1), ymd(1999, 7, 1), ymd(1998, 7, 1), ymd(1999, 7, 1));
+    assert_close(yf, 150.0 / 365.0);
+    let yf = icma.time_fraction_with_reference(ymd(2024, 1, 15), ymd(2024, 3, 1), ymd(2023, 9, 1), ymd(2024, 3, 1));
+    assert_close(yf, 46.0 / (182.0 * 2.0));
+}
+
+#[test]
+fn actual_actual_afb() {
+    let afb = ActualActualAFB;
+    assert_close(afb.time_fraction(ymd(2003, 11, 1), ymd(2004, 5, 1)), 182.0 / 366.0);
+    assert_close(afb.time_fraction(ymd(1999, 2, 1), ymd(1999, 7, 1)), 150.0 / 365.0);
+    assert_close(afb.time_fraction(ymd(2002, 8, 15), ymd(2003, 7, 15)), 334.0 / 365.0);
+    assert_close(afb.time_fraction(ymd(2000, 1, 30), ymd(2000, 6, 30)), 152.0 / 366.0);
+    assert_close(afb.time_fraction(ymd(1999, 11, 30), ymd(2000, 4, 30)), 152.0 / 366.0);
+    assert_close(afb.time_fraction(ymd(2001, 2, 10), ymd(2004, 6, 30)), 3.0 + 140.0 / 365.0);
+    assert_close(afb.time_fraction(ymd(2023, 2, 28), ymd(2024, 2, 29)), 1.0);
+}
+
+#[test]
+fn actual_365_family() {
+    let act365l = Actual365L::new(false);
+    assert_close(act365l.time_fraction(ymd(2023, 12, 1), ymd(2024, 3, 1)), 91.0 / 366.0);
+    assert_close(act365l.time_fraction(ymd(2024, 12, 1), ymd(2025, 3, 1)), 90.0 / 365.0);
+    let act365l_annual = Actual365L::new(true);
+    assert_close(act365l_annual.time_fraction(ymd(2023, 3, 1), ymd(2024, 3, 1)), 1.0);
+    assert_close(act365l_annual.time_fraction(ymd(2024, 3, 1), ymd(2025, 3, 1)), 1.0);
+    assert_close(act365l_annual.time_fraction(ymd(2023, 6, 1), ymd(2024, 2, 28)), 272.0 / 365.0);
+    let end_dates = vec![ymd(2024, 3, 1), ymd(2025, 3, 1)];
+    let tfv = act365l.time_fraction_vector(ymd(2023, 12, 1), &end_dates);
+    assert_close(tfv[0], 91.0 / 366.0);
+    assert_close(tfv[1], 456.0 / 365.0);
+
+    let nl365 = DayCountConvention::NoLeap365.time_fraction_calc().unwrap();
+    assert_close(nl365.time_fraction(ymd(2024, 1, 1), ymd(2025, 1, 1)), 1.0);
+    assert_close(nl365.time_fraction(ymd(2024, 2, 28), ymd(2024, 3, 1)), 1.0 / 365.0);
+    assert_eq!(NoLeapCounter.day_count_vector(ymd(2023, 1, 1), &[ymd(2024, 1, 1), ymd(2025, 1, 1), ymd(2029, 1, 1)]), vec![365, 730, 2190]);
+
+    let act364 = DayCountConvention::Actual364.time_fraction_calc().unwrap();
+    assert_close(act364.time_fraction(ymd(2024, 1, 1), ymd(2024, 12, 30)), 1.0);
+}